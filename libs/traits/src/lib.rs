@@ -5,6 +5,10 @@ use sp_std::vec::Vec;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use pallet_grandpa::AuthorityId as GrandpaId;
 
+mod url ;
+
+pub use url::canonicalize_url ;
+
 pub trait OwnershipRegistry<T:Config> {
 
     fn get_pot_id() -> T::AccountId ;
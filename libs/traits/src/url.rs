@@ -0,0 +1,292 @@
+use sp_std::vec::Vec;
+use sp_std::str;
+
+
+// Query parameters that only track where visitors come from
+const TRACKING_PARAMS: [&str; 7] = ["fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "igshid"] ;
+
+const TRACKING_PREFIX: &str = "utm_" ;
+
+// Punycode parameters (RFC 3492)
+const BASE: u32 = 36 ;
+const TMIN: u32 = 1 ;
+const TMAX: u32 = 26 ;
+const SKEW: u32 = 38 ;
+const DAMP: u32 = 700 ;
+const INITIAL_BIAS: u32 = 72 ;
+const INITIAL_N: u32 = 128 ;
+
+
+// Canonical form of a URL, used as storage key by the likes and owners pallets:
+// - scheme and host are lower-cased
+// - non-ASCII host labels are lower-cased and punycode-encoded (xn--)
+// - default ports (80 for http, 443 for https) are removed
+// - trailing slashes at the end of the path are removed
+// - tracking parameters (utm_*, fbclid, gclid...) and the fragment are removed
+// Inputs without a scheme or that are not valid UTF-8 are only trimmed.
+// Canonicalizing a canonical URL returns it unchanged.
+// Limitation: hosts are not mapped with IDNA (UTS #46) nor normalized to NFC,
+// non-ASCII labels are only lower-cased before punycode. A host typed with decomposed accents,
+// full-width letters or characters that IDNA maps (eg. ß) gets a different key than its
+// browser form, so clients should submit hosts as browsers display them.
+pub fn canonicalize_url(url: &[u8]) -> Vec<u8> {
+    let text = match str::from_utf8(url) {
+        Ok(text) => text.trim(),
+        Err(_) => return url.into()
+    } ;
+    let scheme_end = match text.find("://") {
+        Some(idx) if idx>0 => idx,
+        _ => return text.as_bytes().into()
+    } ;
+    let scheme = &text[..scheme_end] ;
+    let rest = &text[scheme_end+3..] ;
+
+    // Split authority, path and query, the fragment is dropped
+    let rest = match rest.find('#') {
+        Some(idx) => &rest[..idx],
+        None => rest
+    } ;
+    let authority_end = rest.find(|c: char| c=='/' || c=='?').unwrap_or(rest.len()) ;
+    let authority = &rest[..authority_end] ;
+    let rest = &rest[authority_end..] ;
+    let (path, query) = match rest.find('?') {
+        Some(idx) => (&rest[..idx], &rest[idx+1..]),
+        None => (rest, "")
+    } ;
+
+    let mut ans: Vec<u8> = Vec::with_capacity(url.len()) ;
+
+    // Scheme
+    let scheme_lower = scheme.to_ascii_lowercase() ;
+    ans.extend_from_slice(scheme_lower.as_bytes()) ;
+    ans.extend_from_slice(b"://") ;
+
+    // User info is kept as is
+    let host_port = match authority.rfind('@') {
+        Some(idx) => {
+            ans.extend_from_slice(authority[..idx+1].as_bytes()) ;
+            &authority[idx+1..]
+        },
+        None => authority
+    } ;
+
+    // Host and port
+    let port_idx = if host_port.starts_with('[') {
+        host_port.find(']').and_then(|idx| host_port[idx..].find(':').map(|x| idx+x))
+    } else {
+        host_port.rfind(':')
+    } ;
+    let (host, port) = match port_idx {
+        Some(idx) => (&host_port[..idx], &host_port[idx+1..]),
+        None => (host_port, "")
+    } ;
+    push_host(&mut ans, host) ;
+    let default_port = (scheme_lower=="http" && port=="80") || (scheme_lower=="https" && port=="443") ;
+    if !port.is_empty() && !default_port {
+        ans.push(b':') ;
+        ans.extend_from_slice(port.as_bytes()) ;
+    }
+
+    // Path
+    ans.extend_from_slice(path.trim_end_matches('/').as_bytes()) ;
+
+    // Query without tracking parameters
+    let mut first = true ;
+    for param in query.split('&') {
+        if param.is_empty() || is_tracking_param(param) {
+            continue ;
+        }
+        ans.push(if first { b'?' } else { b'&' }) ;
+        ans.extend_from_slice(param.as_bytes()) ;
+        first = false ;
+    }
+
+    ans
+}
+
+fn is_tracking_param(param: &str) -> bool {
+    let key = match param.find('=') {
+        Some(idx) => &param[..idx],
+        None => param
+    } ;
+    let key = key.to_ascii_lowercase() ;
+    key.starts_with(TRACKING_PREFIX) || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+fn push_host(ans: &mut Vec<u8>, host: &str) {
+    for (i, label) in host.split('.').enumerate() {
+        if i>0 {
+            ans.push(b'.') ;
+        }
+        if label.is_ascii() {
+            ans.extend_from_slice(label.to_ascii_lowercase().as_bytes()) ;
+        } else {
+            let chars: Vec<char> = label.chars().flat_map(|c| c.to_lowercase()).collect() ;
+            match punycode(&chars) {
+                Some(encoded) => {
+                    ans.extend_from_slice(b"xn--") ;
+                    ans.extend_from_slice(&encoded) ;
+                },
+                None => ans.extend_from_slice(label.as_bytes())
+            }
+        }
+    }
+}
+
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 } ;
+    delta += delta / num_points ;
+    let mut k = 0 ;
+    while delta > ((BASE - TMIN) * TMAX) / 2 {
+        delta /= BASE - TMIN ;
+        k += BASE ;
+    }
+    k + (((BASE - TMIN + 1) * delta) / (delta + SKEW))
+}
+
+fn encode_digit(digit: u32) -> u8 {
+    if digit<26 {
+        b'a' + digit as u8
+    } else {
+        b'0' + (digit - 26) as u8
+    }
+}
+
+// Punycode encoding of a label (RFC 3492), None on overflow
+fn punycode(input: &[char]) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = input.iter().filter(|c| c.is_ascii()).map(|c| *c as u8).collect() ;
+    let basic = output.len() as u32 ;
+    if basic>0 {
+        output.push(b'-') ;
+    }
+    let total = input.len() as u32 ;
+    let mut n = INITIAL_N ;
+    let mut delta: u32 = 0 ;
+    let mut bias = INITIAL_BIAS ;
+    let mut h = basic ;
+    while h<total {
+        let m = input.iter().map(|c| *c as u32).filter(|c| *c>=n).min()? ;
+        delta = delta.checked_add((m - n).checked_mul(h + 1)?)? ;
+        n = m ;
+        for c in input {
+            let c = *c as u32 ;
+            if c<n {
+                delta = delta.checked_add(1)? ;
+            }
+            if c==n {
+                let mut q = delta ;
+                let mut k = BASE ;
+                loop {
+                    let t = if k<=bias { TMIN } else if k>=bias + TMAX { TMAX } else { k - bias } ;
+                    if q<t {
+                        break ;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t))) ;
+                    q = (q - t) / (BASE - t) ;
+                    k += BASE ;
+                }
+                output.push(encode_digit(q)) ;
+                bias = adapt(delta, h + 1, h==basic) ;
+                delta = 0 ;
+                h += 1 ;
+            }
+        }
+        delta += 1 ;
+        n += 1 ;
+    }
+    Some(output)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::* ;
+
+    fn canonical(url: &str) -> String {
+        String::from_utf8(canonicalize_url(url.as_bytes())).unwrap()
+    }
+
+    fn encode(codepoints: &[u32]) -> String {
+        let chars: Vec<char> = codepoints.iter().map(|c| char::from_u32(*c).unwrap()).collect() ;
+        String::from_utf8(punycode(&chars).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn punycode_rfc3492_samples() {
+        // (A) Arabic (Egyptian)
+        assert_eq!(encode(&[0x0644, 0x064A, 0x0647, 0x0645, 0x0627, 0x0628, 0x062A, 0x0643, 0x0644,
+            0x0645, 0x0648, 0x0634, 0x0639, 0x0631, 0x0628, 0x064A, 0x061F]), "egbpdaj6bu4bxfgehfvwxn") ;
+        // (B) Chinese (simplified)
+        assert_eq!(encode(&[0x4ED6, 0x4EEC, 0x4E3A, 0x4EC0, 0x4E48, 0x4E0D, 0x8BF4, 0x4E2D, 0x6587]),
+            "ihqwcrb4cv8a8dqg056pqjye") ;
+        // (L) 3<nen>B<gumi><kinpachi><sensei>
+        assert_eq!(encode(&[0x0033, 0x5E74, 0x0042, 0x7D44, 0x91D1, 0x516B, 0x5148, 0x751F]),
+            "3B-ww4c5e180e575a65lsy2b") ;
+        // (M) <amuro><namie>-with-SUPER-MONKEYS
+        assert_eq!(encode(&[0x5B89, 0x5BA4, 0x5948, 0x7F8E, 0x6075, 0x002D, 0x0077, 0x0069, 0x0074,
+            0x0068, 0x002D, 0x0053, 0x0055, 0x0050, 0x0045, 0x0052, 0x002D, 0x004D, 0x004F, 0x004E,
+            0x004B, 0x0045, 0x0059, 0x0053]), "-with-SUPER-MONKEYS-pc58ag80a8qai00g7n9n") ;
+        // (Q) <pafii>de<runba>
+        assert_eq!(encode(&[0x30D1, 0x30D5, 0x30A3, 0x30FC, 0x0064, 0x0065, 0x30EB, 0x30F3, 0x30D0]),
+            "de-jg4avhby1noc0d") ;
+        // (R) <sono><supiido><de>
+        assert_eq!(encode(&[0x305D, 0x306E, 0x30B9, 0x30D4, 0x30FC, 0x30C9, 0x3067]), "d9juau41awczczp") ;
+    }
+
+    #[test]
+    fn canonicalize_url_encodes_international_hosts() {
+        assert_eq!(canonical("https://bücher.example/"), "https://xn--bcher-kva.example") ;
+        assert_eq!(canonical("https://MÜNCHEN.de/Stadt"), "https://xn--mnchen-3ya.de/Stadt") ;
+    }
+
+    #[test]
+    fn canonicalize_url_normalizes_scheme_host_and_port() {
+        assert_eq!(canonical("HTTPS://WWW.Example.COM/Path"), "https://www.example.com/Path") ;
+        assert_eq!(canonical("https://example.com:443/a"), "https://example.com/a") ;
+        assert_eq!(canonical("http://example.com:80/a"), "http://example.com/a") ;
+        assert_eq!(canonical("http://example.com:443/a"), "http://example.com:443/a") ;
+        assert_eq!(canonical("https://example.com:8443/a"), "https://example.com:8443/a") ;
+        assert_eq!(canonical("https://user@example.com:443"), "https://user@example.com") ;
+        assert_eq!(canonical("https://[::1]:443/a"), "https://[::1]/a") ;
+    }
+
+    #[test]
+    fn canonicalize_url_removes_trailing_slashes_fragment_and_tracking() {
+        assert_eq!(canonical("https://example.com/"), "https://example.com") ;
+        assert_eq!(canonical("https://example.com/blog//"), "https://example.com/blog") ;
+        assert_eq!(canonical("https://example.com/a#section"), "https://example.com/a") ;
+        assert_eq!(canonical("https://example.com/a?utm_source=x&id=3&FBCLID=y&gclid=z"), "https://example.com/a?id=3") ;
+        assert_eq!(canonical("https://example.com/a?utm_medium=x"), "https://example.com/a") ;
+        assert_eq!(canonical("https://example.com/a?id=3#top"), "https://example.com/a?id=3") ;
+    }
+
+    #[test]
+    fn canonicalize_url_keeps_prefix_wildcards() {
+        assert_eq!(canonical("https://Example.com/*"), "https://example.com/*") ;
+        assert_eq!(canonical("https://example.com/blog/*?utm_source=x"), "https://example.com/blog/*") ;
+        assert_eq!(canonical("https://example.com:443/blog/*#a"), "https://example.com/blog/*") ;
+    }
+
+    #[test]
+    fn canonicalize_url_trims_other_inputs() {
+        assert_eq!(canonical("  example.com/a/  "), "example.com/a/") ;
+        assert_eq!(canonicalize_url(&[0xff, 0x2f]), [0xff, 0x2f].to_vec()) ;
+    }
+
+    #[test]
+    fn canonicalize_url_is_idempotent() {
+        let urls = [
+            "HTTPS://WWW.Example.COM:443/Path/?utm_source=x&b=2#frag",
+            "https://bücher.example/",
+            "https://xn--bcher-kva.example",
+            "http://example.com:8080/a//?a=1&&b=2",
+            "https://user@[::1]:443/",
+            "https://example.com/blog/*",
+            "example.com/a/",
+        ] ;
+        for url in urls.iter() {
+            let once = canonicalize_url(url.as_bytes()) ;
+            assert_eq!(canonicalize_url(&once), once, "{}", url) ;
+        }
+    }
+}
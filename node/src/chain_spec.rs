@@ -1,7 +1,8 @@
 use sp_core::{Pair, Public, sr25519};
 use wika_runtime::{
 	AccountId, BalancesConfig, GenesisConfig,
-	SudoConfig, SystemConfig, AuraConfig, GrandpaConfig, AuthoritiesConfig, WASM_BINARY, Signature
+	SudoConfig, SystemConfig, AuraConfig, GrandpaConfig, AuthoritiesConfig, WASM_BINARY, Signature,
+	LikesConfig, OwnersConfig
 };
 use sp_runtime::traits::{Verify, IdentifyAccount};
 use sc_service::ChainType;
@@ -69,6 +70,14 @@ const BALANCE_UNIT: u128 = 1000000000000 ;
 
 const INITIAL_AUTHORITIES_BALANCE: u128 = 1000 * BALANCE_UNIT;
 
+const LIKE_PRICE: u128 = BALANCE_UNIT ;
+
+const REQUEST_PRICE: u128 = 5 * BALANCE_UNIT ;
+
+const CHALLENGE_BOND: u128 = 10 * BALANCE_UNIT ;
+
+const VERIFIER_BOND: u128 = 100 * BALANCE_UNIT ;
+
 
 fn initial_nodes_dev() -> Vec<([u8; 32],[u8; 32])> {
 	vec![
@@ -130,6 +139,7 @@ pub fn dev_config() -> Result<ChainSpec, String> {
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			&nodes,
 			list_to_accounts(&nodes),
+			list_to_accounts(&nodes),
 			true,
 		),
 		// Bootnodes
@@ -160,6 +170,8 @@ pub fn test_config() -> Result<ChainSpec, String> {
 			get_account_id_from_address::<sr25519::Public>(&hex!("56442d2ddbace4927f284c799c0e706ce7e0df06f395e2c1bbfb967ece5cf053")),
 			&nodes,
 			list_to_accounts(&nodes),
+			// Verifiers register themselves with a bond
+			vec![],
 			true,
 		),
 		// Bootnodes
@@ -188,6 +200,8 @@ pub fn main_config() -> Result<ChainSpec, String> {
 			get_account_id_from_address::<sr25519::Public>(&hex!("a47f12db28111cfc0052494f434f4bf2cd316dcd13852d6f64e538d861c23534")),
 			&nodes,
 			list_to_accounts(&nodes),
+			// Verifiers register themselves with a bond
+			vec![],
 			true,
 		),
 		// Bootnodes
@@ -209,6 +223,7 @@ fn wika_genesis(
 	root_key: AccountId,
 	initial_authorities: &Vec<([u8; 32],[u8; 32])>,
 	endowed_accounts: Vec<AccountId>,
+	initial_verifiers: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -232,5 +247,35 @@ fn wika_genesis(
 		authorities: AuthoritiesConfig {
 			keys: initial_authorities.clone(),
 		},
+		likes: LikesConfig {
+			like_price: LIKE_PRICE,
+			max_likes: 100,
+			share_author: 33,
+			share_referrer: 33,
+			share_previous_likers: 33,
+			num_rounds_to_reward_likers: 4,
+		},
+		owners: OwnersConfig {
+			request_price: REQUEST_PRICE,
+			num_blocks_to_commit: 5,
+			num_blocks_to_reveal: 5,
+			num_blocks_to_delete: 100,
+			prct_needed_for_agreement: 66,
+			majority_min: 1,
+			ownership_ttl: 0,
+			renew_price_prct: 50,
+			challenge_bond: CHALLENGE_BOND,
+			verifiers_share_prct: 50,
+			max_retries: 1,
+			no_reveal_refund_prct: 100,
+			inconclusive_refund_prct: 50,
+			verifier_bond: VERIFIER_BOND,
+			unbonding_period: 14_400,
+			slash_prct: 10,
+			max_minority_prct: 34,
+			max_missed_reveals_prct: 50,
+			verifiers: initial_verifiers,
+			owners: vec![],
+		},
 	}
 }
@@ -0,0 +1,105 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_std::vec::Vec;
+
+
+
+/// Likes state of a URL.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct UrlInfo<AccountId, Balance> {
+	/// Number of likes received by the URL.
+	pub num_likes: u64,
+	/// First liker in line.
+	pub first_in_line: AccountId,
+	/// Last liker in line.
+	pub last_in_line: AccountId,
+	/// Rewards owed to the likers in line and not paid yet.
+	pub owed_rewards: Balance,
+}
+
+/// Like record of an account for a URL.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LikeInfo<AccountId, Balance> {
+	/// Number of likes at the URL when this like was submitted.
+	pub previous_likes: u64,
+	/// Number of likes submitted.
+	pub num_likes: u32,
+	/// Rewards still to be received, including the ones ready to claim.
+	pub remaining_rewards: Balance,
+	/// Reward rounds still to be received.
+	pub remaining_rounds: u32,
+	/// Rewards ready to claim.
+	pub claimable_rewards: Balance,
+	/// Next liker in line, if any.
+	pub next_in_line: Option<AccountId>,
+}
+
+/// Entry of the payout chain of a URL.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct QueueItem<AccountId, Balance> {
+	/// Liker waiting for rewards.
+	pub account: AccountId,
+	/// Rewards still to be paid by the line.
+	pub remaining_rewards: Balance,
+}
+
+
+/// Past like round of an account for a URL.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LikeRound<Balance> {
+	/// Number of likes at the URL when this like was submitted.
+	pub previous_likes: u64,
+	/// Number of likes submitted.
+	pub num_likes: u32,
+	/// Rewards received per round.
+	pub round_rewards: Balance,
+}
+
+/// Likes of an account archived once their lines paid them in full.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LikeSummary<Balance> {
+	/// Number of likes archived.
+	pub total_liked: u64,
+	/// Rewards earned by the likes.
+	pub total_earned: Balance,
+}
+
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the likes pallet.
+	pub trait LikesApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Likes state of a URL.
+		fn get_url(url: Vec<u8>) -> Option<UrlInfo<AccountId, Balance>> ;
+
+		/// Like record of an account for a URL.
+		fn get_like(account: AccountId, url: Vec<u8>) -> Option<LikeInfo<AccountId, Balance>> ;
+
+		/// Payout chain of a URL, starting with the first liker in line.
+		fn get_queue(url: Vec<u8>) -> Vec<QueueItem<AccountId, Balance>> ;
+
+		/// Rewards credited to an account as author or referrer and not claimed yet.
+		fn get_pending_rewards(account: AccountId) -> Balance ;
+
+		/// Past like rounds of an account for a URL, oldest first.
+		fn get_like_history(account: AccountId, url: Vec<u8>) -> Vec<LikeRound<Balance>> ;
+
+		/// Likes of an account archived once their lines paid them in full.
+		fn get_like_summary(account: AccountId) -> LikeSummary<Balance> ;
+	}
+}
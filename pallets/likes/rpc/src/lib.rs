@@ -0,0 +1,117 @@
+//! RPC methods to query the likes pallet.
+
+use std::sync::Arc;
+
+use parity_scale_codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_likes_rpc_runtime_api::{
+	LikesApi as LikesRuntimeApi,
+	UrlInfo, LikeInfo, QueueItem, LikeRound, LikeSummary
+};
+
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+
+/// Likes RPC methods.
+#[rpc]
+pub trait LikesApi<BlockHash, AccountId, Balance> {
+	/// Likes state of a URL.
+	#[rpc(name = "likes_getUrl")]
+	fn get_url(&self, url: String, at: Option<BlockHash>) -> Result<Option<UrlInfo<AccountId, Balance>>>;
+
+	/// Like record of an account for a URL.
+	#[rpc(name = "likes_getLike")]
+	fn get_like(&self, account: AccountId, url: String, at: Option<BlockHash>) -> Result<Option<LikeInfo<AccountId, Balance>>>;
+
+	/// Payout chain of a URL, starting with the first liker in line.
+	#[rpc(name = "likes_getQueue")]
+	fn get_queue(&self, url: String, at: Option<BlockHash>) -> Result<Vec<QueueItem<AccountId, Balance>>>;
+
+	/// Rewards credited to an account as author or referrer and not claimed yet.
+	#[rpc(name = "likes_getPendingRewards")]
+	fn get_pending_rewards(&self, account: AccountId, at: Option<BlockHash>) -> Result<Balance>;
+
+	/// Past like rounds of an account for a URL, oldest first.
+	#[rpc(name = "likes_getLikeHistory")]
+	fn get_like_history(&self, account: AccountId, url: String, at: Option<BlockHash>) -> Result<Vec<LikeRound<Balance>>>;
+
+	/// Likes of an account archived once their lines paid them in full.
+	#[rpc(name = "likes_getLikeSummary")]
+	fn get_like_summary(&self, account: AccountId, at: Option<BlockHash>) -> Result<LikeSummary<Balance>>;
+}
+
+
+/// Implementation of the likes RPC methods.
+pub struct Likes<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Likes<C, B> {
+	/// Create a new instance from a client.
+	pub fn new(client: Arc<C>) -> Self {
+		Likes { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query the likes pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance> LikesApi<<Block as BlockT>::Hash, AccountId, Balance> for Likes<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: LikesRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec,
+{
+	fn get_url(&self, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<UrlInfo<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_url(&at, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_like(&self, account: AccountId, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<LikeInfo<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_like(&at, account, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_queue(&self, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<QueueItem<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_queue(&at, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_pending_rewards(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_pending_rewards(&at, account).map_err(runtime_error)
+	}
+
+	fn get_like_history(&self, account: AccountId, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<LikeRound<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_like_history(&at, account, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_like_summary(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<LikeSummary<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_like_summary(&at, account).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,87 @@
+//! Benchmarking setup for pallet-likes
+
+use super::* ;
+
+use frame_benchmarking::{benchmarks, account, whitelisted_caller} ;
+use frame_system::RawOrigin ;
+
+const SEED: u32 = 0 ;
+const URL: &[u8] = b"https://www.wika.network/benchmark" ;
+
+// Enough balance to like MaxLikes many times
+fn fund<T:Config>(account: &T::AccountId) {
+	let amount = num_likes_to_balance::<T>(MaxLikes::get().saturating_mul(1_000), 100) ;
+	T::Currency::make_free_balance_be(account, amount) ;
+}
+
+// Canonical URL number i
+fn bench_url<T:Config>(i: u32) -> Vec<u8> {
+	let mut url = URL.to_vec() ;
+	url.extend_from_slice(b"/") ;
+	url.extend_from_slice(&i.to_le_bytes()) ;
+	canonicalize_url(&url)
+}
+
+// Line of a URL with the caller paid in full first when recycled, then s likers paid in full
+// and a liker waiting for more rewards than one like pays
+fn setup_line<T:Config>(i: u32, caller: &T::AccountId, recycled: bool, s: u32) -> Vec<u8> {
+	let url = bench_url::<T>(i) ;
+	let pot = Module::<T>::get_pot_id() ;
+	let reward = num_likes_to_balance::<T>(1, 100) ;
+	let waiting = num_likes_to_balance::<T>(MaxLikes::get(), 100).saturating_mul(4u32.into()) ;
+	let mut line: Vec<T::AccountId> = Vec::new() ;
+	if recycled {
+		line.push(caller.clone()) ;
+	}
+	for i in 0 .. s {
+		line.push(account("paid", i, SEED)) ;
+	}
+	let mut position: BalanceOf<T> = Zero::zero() ;
+	for (i, liker) in line.iter().enumerate() {
+		let next = line.get(i+1).cloned().unwrap_or_else(|| account("waiting", 0, SEED)) ;
+		Likes::<T>::insert(liker, &url, (0, 1, reward, next, position, reward)) ;
+		position = position.saturating_add(reward) ;
+	}
+	let waiting_liker: T::AccountId = account("waiting", 0, SEED) ;
+	Likes::<T>::insert(&waiting_liker, &url, (0, 1, waiting, pot.clone(), position, waiting)) ;
+	let first = line.first().cloned().unwrap_or_else(|| waiting_liker.clone()) ;
+	Urls::<T>::insert(&url, (1, first, waiting_liker, position, position.saturating_add(waiting))) ;
+	url
+}
+
+benchmarks! {
+	like {
+		let r in 0 .. 1 ;
+		let s in 0 .. T::MaxSkippedPerLike::get().saturating_sub(1) ;
+		let caller: T::AccountId = whitelisted_caller() ;
+		fund::<T>(&caller) ;
+		let url = setup_line::<T>(0, &caller, r==1, s) ;
+		let num_likes = MaxLikes::get() ;
+	}: _(RawOrigin::Signed(caller.clone()), url.clone(), url.clone(), num_likes)
+	verify {
+		assert_eq!(Likes::<T>::get(&caller, &url).1, num_likes) ;
+	}
+
+	claim_rewards {
+		let n in 0 .. T::MaxClaimedLikes::get() ;
+		let pot = Module::<T>::get_pot_id() ;
+		fund::<T>(&pot) ;
+		let caller: T::AccountId = whitelisted_caller() ;
+		let reward = num_likes_to_balance::<T>(1, 100) ;
+		PendingRewards::<T>::insert(&caller, reward) ;
+		let mut urls: Vec<Vec<u8>> = Vec::new() ;
+		let waiting: T::AccountId = account("waiting", 0, SEED) ;
+		for i in 0 .. n {
+			// One URL per record, each line paid the caller in full and moved past them
+			let url = bench_url::<T>(i) ;
+			Likes::<T>::insert(&caller, &url, (0, 1, reward, waiting.clone(), BalanceOf::<T>::zero(), reward)) ;
+			Likes::<T>::insert(&waiting, &url, (1, 1, reward, pot.clone(), reward, reward)) ;
+			Urls::<T>::insert(&url, (2, waiting.clone(), pot.clone(), reward, reward.saturating_add(reward))) ;
+			urls.push(url) ;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), urls)
+	verify {
+		assert!(PendingRewards::<T>::get(&caller).is_zero()) ;
+		assert!(!Likes::<T>::contains_key(&caller, &bench_url::<T>(0))) ;
+	}
+}
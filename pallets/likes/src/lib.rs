@@ -4,17 +4,36 @@ use sp_std::prelude::* ;
 use frame_support::{
     decl_module, decl_storage, decl_event, decl_error,
 	ensure, StorageMap,
-	PalletId,
-	traits::{Currency, ExistenceRequirement, Get}
+	PalletId, BoundedVec,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
+	transactional,
+	traits::{Currency, ExistenceRequirement, Get},
+	weights::Weight
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_signed, ensure_root};
 use sp_std::vec::Vec;
 use sp_runtime::{
 	SaturatedConversion,
-	traits::AccountIdConversion
+	DispatchError,
+	traits::{AccountIdConversion, Saturating, Zero}
 };
 
-use wika_traits::OwnershipRegistry ;
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet} ;
+
+use wika_traits::{OwnershipRegistry, canonicalize_url} ;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking ;
+
+#[cfg(test)]
+mod mock ;
+
+#[cfg(test)]
+mod tests ;
+
+pub mod weights ;
+
+pub use weights::WeightInfo ;
 
 
 
@@ -25,6 +44,11 @@ pub trait Config: frame_system::Config {
 	type Currency: Currency<Self::AccountId> ;
 	type MaxLengthURL: Get<u8> ;
 	type OwnershipRegistry: OwnershipRegistry<Self> ;
+	type MaxClaimedLikes: Get<u32> ;
+	type MaxSkippedPerLike: Get<u32> ;
+	type MaxLikeHistory: Get<u32> ;
+	type MaxLikesPerBatch: Get<u32> ;
+	type WeightInfo: WeightInfo ;
 }
 
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance ;
@@ -36,18 +60,58 @@ fn u128_to_balance<T:Config>(input: u128) -> BalanceOf<T> {
 
 fn num_likes_to_price<T:Config>(num_likes: u32) -> u128 {
 	let num_likes_u128: u128 = num_likes.into() ;
-	LikePrice::get() * num_likes_u128
+	LikePrice::get().saturating_mul(num_likes_u128)
 }
 
 fn num_likes_to_balance<T:Config>(num_likes: u32, share: u8) -> BalanceOf<T> {
 	let price_u128: u128 = num_likes_to_price::<T>(num_likes) ;
 	let share_u128: u128 = share.into() ;
-	let value = price_u128 * share_u128 / 100 ;
+	let value = price_u128.saturating_mul(share_u128) / 100 ;
 	return u128_to_balance::<T>(value) ;
 }
 
 const PALLET_ID: PalletId = PalletId(*b"LIKE_ME!");
 
+// Upper bounds of LikePrice and MaxLikes,
+// so that LikePrice * MaxLikes * 100 stays far below u128::MAX
+const MAX_LIKE_PRICE: u128 = 1_000_000_000_000_000_000_000_000 ;
+const MAX_MAX_LIKES: u32 = 1_000_000 ;
+
+// Version of the storage layout, see on_runtime_upgrade
+const STORAGE_VERSION: u8 = 2 ;
+
+// Like record, see Likes storage for details
+type LikeOf<T> = (u64, u32, BalanceOf<T>, <T as frame_system::Config>::AccountId, BalanceOf<T>, BalanceOf<T>) ;
+
+// Layout of the storage before v2, used by the migrations that run first
+mod v0 {
+	use super::* ;
+	use frame_support::{generate_storage_alias, Blake2_128Concat} ;
+
+	// Like record before v2
+	// - u64: Number of previous likes at the URL when the user submitted his.
+	// - u32: Number of likes
+	// - u32: Remaining likes to be received as rewards
+	// - AccountId: Next liker in line
+	pub type LikeOf<T> = (u64, u32, u32, <T as frame_system::Config>::AccountId) ;
+
+	generate_storage_alias!(
+		Likes, Urls<T: Config> => Map<(Vec<u8>, Blake2_128Concat), (u64, T::AccountId, T::AccountId)>
+	) ;
+	generate_storage_alias!(
+		Likes, Likes<T: Config> => DoubleMap<(T::AccountId, Blake2_128Concat), (Vec<u8>, Blake2_128Concat), LikeOf<T>>
+	) ;
+}
+
+// Snapshot of the economic parameters, used to report changes
+// - u128: LikePrice
+// - u32: MaxLikes
+// - u8: ShareAuthor
+// - u8: ShareReferrer
+// - u8: SharePreviousLikers
+// - u8: NumRoundsToRewardLikers
+pub type LikesParameters = (u128, u32, u8, u8, u8, u8) ;
+
 
 
 // Pallets use events to inform users when important changes are made.
@@ -55,9 +119,14 @@ const PALLET_ID: PalletId = PalletId(*b"LIKE_ME!");
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event! {
     pub enum Event<T> where
-    		AccountId = <T as frame_system::Config>::AccountId {
+    		AccountId = <T as frame_system::Config>::AccountId,
+    		Balance = BalanceOf<T> {
         /// Event emitted when a like is processed. [who, url]
         Liked(AccountId, Vec<u8>, u32),
+        /// Event emitted when the economic parameters are changed. [old, new]
+        ParametersUpdated(LikesParameters, LikesParameters),
+        /// Event emitted when rewards are withdrawn. [who, amount]
+        RewardsClaimed(AccountId, Balance),
     }
 }
 
@@ -71,7 +140,21 @@ decl_error! {
         /// Sender already in queue.
         AlreadyInQueue,
         /// URL is too long.
-        UrlTooLong
+        UrlTooLong,
+        /// Like price must be positive and at most MAX_LIKE_PRICE.
+        InvalidLikePrice,
+        /// Max likes must be positive and at most MAX_MAX_LIKES.
+        InvalidMaxLikes,
+        /// Shares must add up to 100 or less.
+        InvalidShares,
+        /// Number of rounds must be positive.
+        InvalidNumRounds,
+        /// Number of URLs to claim must not exceed MaxClaimedLikes.
+        InvalidClaimSize,
+        /// No rewards to claim.
+        NoRewardsToClaim,
+        /// Batch must contain between 1 and MaxLikesPerBatch likes.
+        InvalidBatchSize
     }
 }
 
@@ -80,38 +163,66 @@ decl_error! {
 decl_storage! {
     trait Store for Module<T: Config> as Likes {
 
+    	// Version of the storage layout
+    	StorageVersion: u8 = 0 ;
+
     	// Number of URLs liked so far
     	UrlCount: u128 = 0 ;
 
     	// Price to submit 1 Like
-    	LikePrice: u128 = 1_000_000_000_000;
+    	LikePrice get(fn like_price) config(): u128 = 1_000_000_000_000;
 
     	// Maximum number of likes allowed
-    	MaxLikes: u32 = 100 ;
+    	MaxLikes get(fn max_likes) config(): u32 = 100 ;
 
     	// How likes are split amongst authors, referrers and previous likers
-    	ShareAuthor: u8 = 33 ;
-    	ShareReferrer: u8 = 33 ;
-    	SharePreviousLikers: u8 = 33 ;
+    	ShareAuthor get(fn share_author) config(): u8 = 33 ;
+    	ShareReferrer get(fn share_referrer) config(): u8 = 33 ;
+    	SharePreviousLikers get(fn share_previous_likers) config(): u8 = 33 ;
 
     	// Number of times users will keep receiving rewards once they enter the line
-    	NumRoundsToRewardLikers: u8 = 4 ;
+    	NumRoundsToRewardLikers get(fn num_rounds_to_reward_likers) config(): u8 = 4 ;
 
     	// URL likes
     	// - u64: Number of likes received by this URL.
-    	// - AccountId: Current liker waiting in line to receive their rewards
+    	// - AccountId: First liker in line
+    	//              (the line restarts from the next liker once everyone was paid.)
     	// - AccountId: Last liker in line who will receive rewards
     	//              (this will be used to update the chain when next one comes in.)
-    	Urls: map hasher(blake2_128_concat) Vec<u8> => (u64, T::AccountId, T::AccountId) ;
+    	// - Balance: Rewards paid to the likers in line so far
+    	//            (this is the cursor of the line, the likers it passed can claim their rewards.)
+    	// - Balance: Rewards owed to the likers in line so far
+    	Urls: map hasher(blake2_128_concat) Vec<u8> => (u64, T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>) ;
+
+    	// Rewards credited to an account as author or referrer and not claimed yet
+    	PendingRewards get(fn pending_rewards): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T> ;
 
     	// Like records by URL / USER
     	// - u64: Number of previous likes at the URL when the user submitted his.
     	// - u32: Number of likes
-    	// - u32: Number of rounds during which payback will be received
+    	// - Balance: Rewards still to be received
     	// - AccountId: Next liker in line
-        Likes: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Vec<u8> => (u64, u32, u32, T::AccountId) ;
+    	// - Balance: Position of the next reward in the line
+    	//            (rewards are due once the rewards paid to the line pass this position.)
+    	// - Balance: Rewards received per round, fixed when the like was submitted
+        Likes: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Vec<u8> => LikeOf<T> ;
+
+    	// Archived likes by USER
+    	// - u64: Number of likes in the records moved out of Likes
+    	// - Balance: Rewards earned, counted when they are credited or claimed
+    	LikeSummary get(fn like_summary): map hasher(blake2_128_concat) T::AccountId => (u64, BalanceOf<T>) ;
+
+    	// Past like rounds by USER / URL, oldest first, at most MaxLikeHistory of them
+    	// (older rounds are dropped and only remain counted in LikeSummary.)
+    	// - u64: Number of previous likes at the URL when the like was submitted
+    	// - u32: Number of likes
+    	// - Balance: Rewards received per round
+        LikeHistory: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Vec<u8> => Vec<(u64, u32, BalanceOf<T>)> ;
 
     }
+    add_extra_genesis {
+        build(|config| Module::<T>::initialize_genesis(config))
+    }
 }
 
 
@@ -128,92 +239,342 @@ impl<T: Config> Module<T> {
 		T::Currency::free_balance(&Self::get_pot_id())
 	}
 
-	fn pay(sender: &T::AccountId, num_likes: u32, recipient: &T::AccountId, share: u8) {
-		let amount = num_likes_to_balance::<T>(num_likes, share) ;
-		log::debug!(target: "LIKE", "paying {:?} from {:?} to {:?}", &amount, &sender, &recipient);
-		T::Currency::transfer(sender,
-							  recipient,
-							  amount,
-							  ExistenceRequirement::KeepAlive).expect("balance was already checked");
+	fn get_parameters() -> LikesParameters {
+		(
+			LikePrice::get(),
+			MaxLikes::get(),
+			ShareAuthor::get(),
+			ShareReferrer::get(),
+			SharePreviousLikers::get(),
+			NumRoundsToRewardLikers::get()
+		)
 	}
 
-	fn pay_previous_liker(sender: &T::AccountId, num_likes: u32, recipient: &T::AccountId) {
-		Self::pay(sender, num_likes, recipient, SharePreviousLikers::get()) ;
+	fn validate_like_price(price: u128) -> bool {
+		price>0 && price<=MAX_LIKE_PRICE
 	}
 
-	fn pay_author(sender: &T::AccountId, num_likes: u32, recipient: &T::AccountId) {
-		Self::pay(sender, num_likes, recipient, ShareAuthor::get()) ;
+	fn validate_max_likes(max_likes: u32) -> bool {
+		max_likes>0 && max_likes<=MAX_MAX_LIKES
 	}
 
-	fn pay_referrer(sender: &T::AccountId, num_likes: u32, recipient: &T::AccountId) {
-		Self::pay(sender, num_likes, recipient, ShareReferrer::get()) ;
+	fn validate_shares(author: u8, referrer: u8, previous_likers: u8) -> bool {
+		let total: u16 = u16::from(author) + u16::from(referrer) + u16::from(previous_likers) ;
+		total <= 100
 	}
 
-	fn pay_extra(sender: &T::AccountId, num_likes: u32, recipient: &T::AccountId) {
-		let share = 100 - (SharePreviousLikers::get() + ShareAuthor::get() + ShareReferrer::get()) ;
-		Self::pay(sender, num_likes, recipient, share) ;
+	// Rewards of a like record that the line already paid for
+	fn due_rewards(record: &LikeOf<T>, paid: BalanceOf<T>) -> BalanceOf<T> {
+		paid.saturating_sub(record.4).min(record.2)
 	}
 
-	fn pay_author_referrer_and_extra(sender: &T::AccountId, url: &Vec<u8>, url_ref: &Vec<u8>, num_likes: u32) {
-		// Pay author
-		let author = T::OwnershipRegistry::get_owner(url) ;
-		Self::pay_author(&sender, num_likes, &author) ;
+	// Like record after its due rewards were withdrawn
+	fn settle(record: &LikeOf<T>, due: BalanceOf<T>) -> LikeOf<T> {
+		(record.0, record.1, record.2.saturating_sub(due), record.3.clone(), record.4.saturating_add(due), record.5)
+	}
 
-		// Pay referrer
-		let referrer = T::OwnershipRegistry::get_owner(url_ref) ;
-		Self::pay_referrer(&sender, num_likes, &referrer) ;
+	// Likes state of a URL, used by the runtime API
+	// - u64: Number of likes received by this URL
+	// - AccountId: First liker in line
+	// - AccountId: Last liker in line
+	// - Balance: Rewards owed to the likers in line and not paid yet
+	pub fn get_url(url: &Vec<u8>) -> Option<(u64, T::AccountId, T::AccountId, BalanceOf<T>)> {
+		let url = &canonicalize_url(url) ;
+		if !Urls::<T>::contains_key(url) {
+			return None ;
+		}
+		let (num_likes, first_in_line, last_in_line, paid, owed) = Urls::<T>::get(url) ;
+		// The pot as last in line means there is only one liker in the chain
+		let last_in_line = if last_in_line==Self::get_pot_id() {
+			first_in_line.clone()
+		} else {
+			last_in_line
+		} ;
+		Some((num_likes, first_in_line, last_in_line, owed.saturating_sub(paid)))
+	}
 
-		// Send extra tip to the pot
+	// Like record of an account for a URL, used by the runtime API
+	// - u64: Number of previous likes at the URL when the like was submitted
+	// - u32: Number of likes
+	// - Balance: Rewards still to be received, including the ones ready to claim
+	// - u32: Remaining reward rounds
+	// - Balance: Rewards ready to claim
+	// - Option<AccountId>: Next liker in line
+	pub fn get_like(account: &T::AccountId, url: &Vec<u8>)
+		-> Option<(u64, u32, BalanceOf<T>, u32, BalanceOf<T>, Option<T::AccountId>)> {
+		let url = &canonicalize_url(url) ;
+		if !Likes::<T>::contains_key(account, url) {
+			return None ;
+		}
+		let record = Likes::<T>::get(account, url) ;
+		let claimable = Self::due_rewards(&record, Urls::<T>::get(url).3) ;
+		let (previous_likes, num_likes, remaining, next_in_line, _, round_rewards) = record ;
+		let remaining_rounds: u32 = if round_rewards.is_zero() {
+			0
+		} else {
+			let round_rewards: u128 = round_rewards.saturated_into() ;
+			let remaining: u128 = remaining.saturated_into() ;
+			((remaining + round_rewards - 1) / round_rewards).saturated_into()
+		} ;
+		let next_in_line = if next_in_line==Self::get_pot_id() {
+			None
+		} else {
+			Some(next_in_line)
+		} ;
+		Some((previous_likes, num_likes, remaining, remaining_rounds, claimable, next_in_line))
+	}
+
+	// Payout chain of a URL, walked from the first liker in line, used by the runtime API
+	// Likers already paid in full by the line are skipped
+	// - AccountId: Liker
+	// - Balance: Rewards still to be paid by the line
+	pub fn get_queue(url: &Vec<u8>) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let url = &canonicalize_url(url) ;
+		let mut ans: Vec<(T::AccountId, BalanceOf<T>)> = sp_std::vec![] ;
+		if !Urls::<T>::contains_key(url) {
+			return ans ;
+		}
 		let pot = Self::get_pot_id() ;
-		Self::pay_extra(&sender, num_likes, &pot) ;
+		let (_, mut account, _, paid, _) = Urls::<T>::get(url) ;
+		while account!=pot && Likes::<T>::contains_key(&account, url) {
+			let record = Likes::<T>::get(&account, url) ;
+			let unpaid = record.2.saturating_sub(Self::due_rewards(&record, paid)) ;
+			if !unpaid.is_zero() {
+				ans.push((account, unpaid)) ;
+			}
+			account = record.3 ;
+		}
+		ans
 	}
 
-	fn pay_previous_likers(sender: &T::AccountId, url: &Vec<u8>, first_recipient: T::AccountId, num_likes: u32)
-		-> (T::AccountId, u32) {
-		log::debug!(target: "LIKE", "Paying the target recipients: {:?}", num_likes);
-		let mut recipient = first_recipient ;
-		let mut remaining_likes: u32 = num_likes ;
-		while (remaining_likes>0) && (recipient!=Self::get_pot_id())  {
-			let recipient_data = Likes::<T>::take(&recipient, &url) ;
-			let recipient_likes = recipient_data.2 ;
-			log::debug!(target: "LIKE", "While loop head...");
-			log::debug!(target: "LIKE", "recicient: {:?}", recipient);
-			log::debug!(target: "LIKE", "recipient_likes: {:?}", recipient_likes);
-			log::debug!(target: "LIKE", "remaining_likes: {:?}", remaining_likes);
-			if remaining_likes >= recipient_likes {
-				// Enough likes to pay this recipient in full
-				log::debug!(target: "LIKE", "CASE A - FullPayment") ;
-				log::debug!(target: "LIKE", "Preparing to transfer from: {:?}", &sender);
-				log::debug!(target: "LIKE", "to: {:?}", recipient);
-				log::debug!(target: "LIKE", "recipient_likes: {:?}", recipient_likes);
-				Self::pay_previous_liker(&sender, recipient_likes, &recipient) ;
-				remaining_likes -= recipient_likes ;
-				// Done with recipient re-inserting with zero balance
-				let recipient_data_update = (recipient_data.0, recipient_data.1, 0, recipient_data.3.clone()) ;
-				Likes::<T>::insert(&recipient, &url, recipient_data_update) ;
-				log::debug!(target: "LIKE", "Recipient data updated: {:?}", &recipient);
-				// Moving to next recipient in line
-				recipient = recipient_data.3 ;
-			} else {
-				// Partial payment for this recipient
-				log::debug!(target: "LIKE", "CASE B - PartialPayment") ;
-				log::debug!(target: "LIKE", "Preparing to transfer from: {:?}", &sender);
-				log::debug!(target: "LIKE", "to: {:?}", &recipient);
-				log::debug!(target: "LIKE", "remaining_likes: {:?}", remaining_likes);
-				Self::pay_previous_liker(&sender, remaining_likes, &recipient) ;
-				let recipient_likes_update = recipient_likes - remaining_likes ;
-				// Update this recipient state
-				let recipient_data_update = (recipient_data.0, recipient_data.1, recipient_likes_update, recipient_data.3) ;
-				Likes::<T>::insert(&recipient, &url, recipient_data_update) ;
-				log::debug!(target: "LIKE", "Recipient data updated: {:?}", &recipient);
-				// Done with payments, will exit the loop with zero remaining likes
-				remaining_likes = 0 ;
+	// Past like rounds of an account for a URL, used by the runtime API
+	// - u64: Number of previous likes at the URL when the like was submitted
+	// - u32: Number of likes
+	// - Balance: Rewards received per round
+	pub fn get_like_history(account: &T::AccountId, url: &Vec<u8>) -> Vec<(u64, u32, BalanceOf<T>)> {
+		LikeHistory::<T>::get(account, &canonicalize_url(url))
+	}
+
+	#[cfg(feature = "std")]
+	fn initialize_genesis(config: &GenesisConfig) {
+		assert!(Self::validate_like_price(config.like_price), "LikePrice must be positive and at most MAX_LIKE_PRICE") ;
+		assert!(Self::validate_max_likes(config.max_likes), "MaxLikes must be positive and at most MAX_MAX_LIKES") ;
+		assert!(Self::validate_shares(config.share_author, config.share_referrer, config.share_previous_likers),
+				"Shares must add up to 100 or less") ;
+		assert!(config.num_rounds_to_reward_likers>0, "NumRoundsToRewardLikers must be positive") ;
+
+		// New chains start with the latest storage layout
+		StorageVersion::set(STORAGE_VERSION) ;
+	}
+
+	// Migration to v1:
+	// Urls and Likes are keyed by canonical URL, duplicates are merged into the canonical entry
+	// This runs on the layout before v2
+	fn migrate_to_v1() -> Weight {
+		log::info!(target: "LIKE", "migrate_to_v1 starting");
+		let mut reads: Weight = 0 ;
+		let mut writes: Weight = 0 ;
+
+		// Like records to move, grouped by URL
+		let mut records: BTreeMap<Vec<u8>, Vec<(T::AccountId, v0::LikeOf<T>)>> = BTreeMap::new() ;
+		for (account, url, record) in v0::Likes::<T>::iter() {
+			reads += 1 ;
+			if canonicalize_url(&url)!=url {
+				records.entry(url).or_insert_with(Vec::new).push((account, record)) ;
 			}
 		}
-		(recipient, remaining_likes)
+
+		// URLs to merge
+		let mut urls: Vec<Vec<u8>> = sp_std::vec![] ;
+		for (url, _) in v0::Urls::<T>::iter() {
+			reads += 1 ;
+			if canonicalize_url(&url)!=url {
+				urls.push(url) ;
+			}
+		}
+
+		let count: Weight = urls.len().saturated_into() ;
+		for url in urls {
+			let url_records = records.remove(&url).unwrap_or_default() ;
+			let num_records: Weight = url_records.len().saturated_into() ;
+			reads += num_records + 2 ;
+			writes += num_records.saturating_mul(3) + 2 ;
+			Self::merge_url(&url, &canonicalize_url(&url), url_records) ;
+		}
+
+		log::info!(target: "LIKE", "migrate_to_v1 urls migrated: {:?}", count);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	// Move the likes of a URL to its canonical form
+	// If the canonical URL was liked too, the line of the duplicate joins the end of its line
+	fn merge_url(url: &Vec<u8>, canonical: &Vec<u8>, records: Vec<(T::AccountId, v0::LikeOf<T>)>) {
+		let pot = Self::get_pot_id() ;
+		let url_data = match v0::Urls::<T>::take(url) {
+			Some(url_data) => url_data,
+			None => return
+		} ;
+		for (account, _) in &records {
+			v0::Likes::<T>::remove(account, url) ;
+		}
+
+		// Canonical URL not liked yet, records are simply moved
+		let (num_likes, first_in_line, last_in_line) = match v0::Urls::<T>::get(canonical) {
+			Some(canonical_data) => canonical_data,
+			None => {
+				v0::Urls::<T>::insert(canonical, url_data) ;
+				for (account, record) in &records {
+					v0::Likes::<T>::insert(account, canonical, record) ;
+				}
+				return ;
+			}
+		} ;
+
+		// Walk the line of the duplicate first to keep its order
+		let mut last = if last_in_line==pot { first_in_line.clone() } else { last_in_line } ;
+		let mut pending: BTreeMap<T::AccountId, v0::LikeOf<T>> = records.into_iter().collect() ;
+		let mut account = url_data.1.clone() ;
+		while account!=pot {
+			let record = match pending.remove(&account) {
+				Some(record) => record,
+				None => break
+			} ;
+			let next = record.3.clone() ;
+			Self::merge_like(&account, canonical, record, &mut last) ;
+			account = next ;
+		}
+
+		// Then the records which are not in line anymore
+		for (account, record) in pending {
+			Self::merge_like(&account, canonical, record, &mut last) ;
+		}
+
+		let last_in_line = if last==first_in_line { pot } else { last } ;
+		v0::Urls::<T>::insert(canonical, (num_likes+url_data.0, first_in_line, last_in_line)) ;
+		UrlCount::mutate(|count| *count = count.saturating_sub(1)) ;
+	}
+
+	// Move a like record to the canonical URL, adding it at the end of the line if it still expects rewards
+	// An account that liked both URLs keeps a single record with both likes
+	fn merge_like(account: &T::AccountId, canonical: &Vec<u8>, record: v0::LikeOf<T>, last: &mut T::AccountId) {
+		let pot = Self::get_pot_id() ;
+		if let Some(mut existing) = v0::Likes::<T>::get(account, canonical) {
+			let in_line = existing.2>0 ;
+			existing.1 += record.1 ;
+			existing.2 += record.2 ;
+			if in_line || record.2==0 {
+				v0::Likes::<T>::insert(account, canonical, existing) ;
+				return ;
+			}
+			existing.3 = pot ;
+			v0::Likes::<T>::insert(account, canonical, existing) ;
+		} else {
+			let in_line = record.2>0 ;
+			v0::Likes::<T>::insert(account, canonical, (record.0, record.1, record.2, pot)) ;
+			if !in_line {
+				return ;
+			}
+		}
+		v0::Likes::<T>::mutate(&*last, canonical, |previous| {
+			if let Some(previous) = previous {
+				previous.3 = account.clone() ;
+			}
+		}) ;
+		*last = account.clone() ;
 	}
 
+	// Migration to v2:
+	// Rewards are recorded as balances fixed when the like was submitted instead of likes
+	// valued at the current price. Existing records are valued at the current price and share,
+	// the only ones known, and take their position in the line in order.
+	// Records paid in full which left their line are archived: they are counted in LikeSummary,
+	// the rewards they received before the migration are not known and not counted.
+	fn migrate_to_v2() -> Weight {
+		log::info!(target: "LIKE", "migrate_to_v2 starting");
+		let mut reads: Weight = 0 ;
+		let mut writes: Weight = 0 ;
+		let pot = Self::get_pot_id() ;
+		let share = SharePreviousLikers::get() ;
+
+		Likes::<T>::translate::<v0::LikeOf<T>, _>(|_, _, (previous_likes, num_likes, remaining, next_in_line)| {
+			reads += 1 ;
+			writes += 1 ;
+			let remaining = num_likes_to_balance::<T>(remaining, share) ;
+			let round_rewards = num_likes_to_balance::<T>(num_likes, share) ;
+			Some((previous_likes, num_likes, remaining, next_in_line, Zero::zero(), round_rewards))
+		}) ;
+
+		let mut in_line: BTreeSet<(T::AccountId, Vec<u8>)> = BTreeSet::new() ;
+		Urls::<T>::translate::<(u64, T::AccountId, T::AccountId), _>(|url, (num_likes, first_in_line, last_in_line)| {
+			reads += 1 ;
+			writes += 1 ;
+			// Positions follow the order of the line
+			let mut owed: BalanceOf<T> = Zero::zero() ;
+			let mut account = first_in_line.clone() ;
+			while account!=pot && Likes::<T>::contains_key(&account, &url) {
+				if !in_line.insert((account.clone(), url.clone())) {
+					break ;
+				}
+				reads += 1 ;
+				writes += 1 ;
+				let mut record = Likes::<T>::get(&account, &url) ;
+				record.4 = owed ;
+				owed = owed.saturating_add(record.2) ;
+				Likes::<T>::insert(&account, &url, &record) ;
+				account = record.3 ;
+			}
+			Some((num_likes, first_in_line, last_in_line, Zero::zero(), owed))
+		}) ;
+
+		// Zero balance records out of their line
+		let mut archived: Vec<(T::AccountId, Vec<u8>, u32)> = sp_std::vec![] ;
+		for (account, url, record) in Likes::<T>::iter() {
+			reads += 1 ;
+			if record.2.is_zero() && !in_line.contains(&(account.clone(), url.clone())) {
+				archived.push((account, url, record.1)) ;
+			}
+		}
+		for (account, url, num_likes) in archived {
+			reads += 1 ;
+			writes += 2 ;
+			LikeSummary::<T>::mutate(&account, |summary| summary.0 = summary.0.saturating_add(num_likes.into())) ;
+			Likes::<T>::remove(&account, &url) ;
+		}
 
+		log::info!(target: "LIKE", "migrate_to_v2 records migrated: {:?}", reads);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	fn pay(sender: &T::AccountId, amount: BalanceOf<T>, recipient: &T::AccountId) -> DispatchResult {
+		log::debug!(target: "LIKE", "paying {:?} from {:?} to {:?}", &amount, &sender, &recipient);
+		T::Currency::transfer(sender,
+							  recipient,
+							  amount,
+							  ExistenceRequirement::KeepAlive)
+	}
+
+	// Add rewards to the balance an account can claim
+	// The owners pot does not claim, its share leaves the likes pot right away
+	fn credit(recipient: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(()) ;
+		}
+		log::debug!(target: "LIKE", "crediting {:?} to {:?}", &amount, &recipient);
+		if *recipient==T::OwnershipRegistry::get_pot_id() {
+			return T::Currency::transfer(&Self::get_pot_id(), recipient, amount, ExistenceRequirement::AllowDeath) ;
+		}
+		PendingRewards::<T>::mutate(recipient, |pending| *pending = pending.saturating_add(amount)) ;
+		Ok(())
+	}
+
+	// Credit the shares of the author and the referrer, the extra share stays in the pot
+	fn credit_author_and_referrer(url: &Vec<u8>, url_ref: &Vec<u8>, num_likes: u32) -> DispatchResult {
+		let author = T::OwnershipRegistry::get_owner(url) ;
+		Self::credit(&author, num_likes_to_balance::<T>(num_likes, ShareAuthor::get()))?;
+
+		let referrer = T::OwnershipRegistry::get_owner(url_ref) ;
+		Self::credit(&referrer, num_likes_to_balance::<T>(num_likes, ShareReferrer::get()))
+	}
 
 	fn add_to_chain(sender: &T::AccountId, url: &Vec<u8>,
 							 first_in_line: T::AccountId, last_in_line: T::AccountId) {
@@ -224,80 +585,127 @@ impl<T: Config> Module<T> {
 		} ;
 		log::debug!(target: "LIKE", "Adding sender to the chain: {:?}", &sender) ;
 		log::debug!(target: "LIKE", "Previous account: {:?}", &account) ;
-		let data = Likes::<T>::take(&account, &url) ;
-		let update = (data.0, data.1, data.2, sender.clone()) ;
-		Likes::<T>::insert(&account, &url, update) ;
+		Likes::<T>::mutate(&account, &url, |data| data.3 = sender.clone()) ;
 	}
 
-	fn update_url(sender: &T::AccountId, url: &Vec<u8>, current_num: u64, num_likes: u32, next_recipient: T::AccountId) {
-		let num_likes_u64: u64 = num_likes.into() ;
-		let num_likes_update: u64 = current_num+num_likes_u64 ;
-		if next_recipient==Self::get_pot_id() {
-			// If we reached pot_id it means we cleared all recipients from this URL
-			// Sender becomes first in line
-			log::debug!(target: "LIKE", "Sender is becoming first in line: {:?}", &sender);
-			let new_url_data = (num_likes_update, &sender, &Self::get_pot_id()) ;
-			Urls::<T>::insert(&url, new_url_data);
-		} else {
-			// Otherwise update first in line and put sender as last
-			log::debug!(target: "LIKE", "Updating first in line and sender") ;
-			let new_url_data = (num_likes_update, &next_recipient, &sender) ;
-			Urls::<T>::insert(&url, new_url_data);
+	// Whether the head of a line moved past a like record
+	fn is_behind_head(url: &Vec<u8>, first_in_line: &T::AccountId, record: &LikeOf<T>) -> bool {
+		*first_in_line==Self::get_pot_id() || Likes::<T>::get(first_in_line, url).4>record.4
+	}
+
+	// Keep a like round in LikeHistory, dropping the oldest ones beyond MaxLikeHistory
+	fn push_history(account: &T::AccountId, url: &Vec<u8>, round: (u64, u32, BalanceOf<T>)) {
+		LikeHistory::<T>::mutate(account, url, |history| {
+			history.push(round) ;
+			let max: usize = T::MaxLikeHistory::get().saturated_into() ;
+			if history.len()>max {
+				let excess = history.len()-max ;
+				history.drain(..excess) ;
+			}
+		}) ;
+	}
 
+	// Move a like record the line paid in full out of Likes
+	// Its remaining rewards are credited, the round is kept in LikeHistory and counted in LikeSummary
+	fn archive_like(account: &T::AccountId, url: &Vec<u8>, record: &LikeOf<T>) {
+		log::debug!(target: "LIKE", "archive_like account: {:?} due: {:?}", &account, &record.2);
+		if !record.2.is_zero() {
+			PendingRewards::<T>::mutate(account, |pending| *pending = pending.saturating_add(record.2)) ;
 		}
-		log::debug!(target: "LIKE", "url state updated: {:?}", &url);
+		LikeSummary::<T>::mutate(account, |(total_liked, total_earned)| {
+			*total_liked = total_liked.saturating_add(record.1.into()) ;
+			*total_earned = total_earned.saturating_add(record.2) ;
+		}) ;
+		Self::push_history(account, url, (record.0, record.1, record.5)) ;
+		Likes::<T>::remove(account, url) ;
+	}
+
+	// Move the head of a line past the likers it paid in full and archive them, at most max_steps of them
+	// Returns the new head, the pot if everyone was paid, and the number of likers archived
+	fn advance_line(url: &Vec<u8>, first_in_line: T::AccountId, paid: BalanceOf<T>, max_steps: u32)
+		-> (T::AccountId, u32) {
+		let pot = Self::get_pot_id() ;
+		let mut account = first_in_line ;
+		let mut steps: u32 = 0 ;
+		while steps<max_steps && account!=pot && Likes::<T>::contains_key(&account, url) {
+			let record = Likes::<T>::get(&account, url) ;
+			if Self::due_rewards(&record, paid)<record.2 {
+				break ;
+			}
+			Self::archive_like(&account, url, &record) ;
+			account = record.3 ;
+			steps += 1 ;
+		}
+		(account, steps)
+	}
+
+	// Archive the record of a liker the line paid in full, so they can like again
+	// Returns the number of likers the head of the line moved past
+	fn recycle_like(sender: &T::AccountId, url: &Vec<u8>, max_steps: u32) -> Result<u32, Error<T>> {
+		let (num_likes, first_in_line, last_in_line, paid, owed) = Urls::<T>::get(url) ;
+
+		// The head moves past the likers paid in full, the sender among them if still in line
+		let (first_in_line, steps) = Self::advance_line(url, first_in_line, paid, max_steps) ;
+		if Likes::<T>::contains_key(sender, url) {
+			let record = Likes::<T>::get(sender, url) ;
+			ensure!(Self::due_rewards(&record, paid)==record.2, Error::<T>::AlreadyInQueue) ;
+			ensure!(Self::is_behind_head(url, &first_in_line, &record), Error::<T>::AlreadyInQueue) ;
+			Self::archive_like(sender, url, &record) ;
+		}
+		Urls::<T>::insert(url, (num_likes, first_in_line, last_in_line, paid, owed)) ;
+		Ok(steps)
 	}
 
-	fn like_existing_url(sender: &T::AccountId, url: &Vec<u8>, ref_url: &Vec<u8>, num_likes: u32) {
+	// Returns the number of likers the head of the line moved past
+	fn like_existing_url(sender: &T::AccountId, url: &Vec<u8>, num_likes: u32, max_steps: u32) -> u32 {
 
 		// Take URL data
 		log::debug!(target: "LIKE", "like_existing_url url: {:?}", &url);
-		let data = Urls::<T>::take(&url) ;
-		let current_total_likes = data.0 ;
+		let (current_total_likes, first_in_line, last_in_line, paid, owed) = Urls::<T>::take(&url) ;
 
-		// Start by creating the Like record for this sender...
-		log::debug!(target: "LIKE", "like_existing_url Creating like record: {:?}", &sender);
-		let rounds: u32 = NumRoundsToRewardLikers::get().into() ;
-		Likes::<T>::insert(&sender, &url, (current_total_likes, num_likes, num_likes*rounds, Self::get_pot_id())) ;
-
-		// Pay the previous likers
-		log::debug!(target: "LIKE", "like_existing_url pay_previous_likers: {:?}", &num_likes);
-		let (next_recipient, remaining_likes) = Self::pay_previous_likers(&sender, url, data.1.clone(), num_likes) ;
-		log::debug!(target: "LIKE", "like_existing_url next_recipient: {:?}", &next_recipient);
-		log::debug!(target: "LIKE", "like_existing_url remaining_likes: {:?}", &remaining_likes);
-
-		// Distribute additional likes
-		if remaining_likes>0 {
-			let previous_liker = Self::get_pot_id() ;
-			Self::pay_previous_liker(&sender, remaining_likes, &previous_liker) ;
-		}
+		// The share of previous likers moves the line forward,
+		// anything beyond what the line is owed stays in the pot
+		let round_rewards = num_likes_to_balance::<T>(num_likes, SharePreviousLikers::get()) ;
+		let paid_update = paid.saturating_add(round_rewards).min(owed) ;
+		log::debug!(target: "LIKE", "like_existing_url paid: {:?} owed: {:?}", &paid_update, &owed);
 
-		// Pay author, referrer and extra
-		Self::pay_author_referrer_and_extra(&sender, url, ref_url, num_likes) ;
+		// Likers paid in full leave the line
+		let (first_in_line, steps) = Self::advance_line(&url, first_in_line, paid_update, max_steps) ;
 
-		// Add this sender in the queue chain
-		Self::add_to_chain(&sender, &url, data.1, data.2) ;
+		// Create the Like record for this sender at the end of the line
+		log::debug!(target: "LIKE", "like_existing_url Creating like record: {:?}", &sender);
+		let rounds: u32 = NumRoundsToRewardLikers::get().into() ;
+		let rewards = round_rewards.saturating_mul(rounds.into()) ;
+		Likes::<T>::insert(&sender, &url,
+			(current_total_likes, num_likes, rewards, Self::get_pot_id(), owed, round_rewards)) ;
 
 		// Update the Url state
-		Self::update_url(&sender, &url, data.0, num_likes, next_recipient) ;
+		let num_likes_u64: u64 = num_likes.into() ;
+		let num_likes_update: u64 = current_total_likes+num_likes_u64 ;
+		let owed_update = owed.saturating_add(rewards) ;
+		if paid_update==owed {
+			// Everyone in line was paid, sender becomes first in line
+			log::debug!(target: "LIKE", "Sender is becoming first in line: {:?}", &sender);
+			Urls::<T>::insert(&url, (num_likes_update, &sender, &Self::get_pot_id(), paid_update, owed_update));
+		} else {
+			// Otherwise add this sender in the queue chain as last
+			Self::add_to_chain(&sender, &url, first_in_line.clone(), last_in_line) ;
+			Urls::<T>::insert(&url, (num_likes_update, &first_in_line, &sender, paid_update, owed_update));
+		}
+		log::debug!(target: "LIKE", "url state updated: {:?}", &url);
+		steps
 	}
 
-	fn like_new_url(sender: &T::AccountId, url: &Vec<u8>, url_ref: &Vec<u8>, num_likes: u32) {
+	fn like_new_url(sender: &T::AccountId, url: &Vec<u8>, num_likes: u32) {
 		// Create the URL record for the first time
 		log::debug!(target: "LIKE", "Creating url state for first time: {:?}", &url);
 		let total_likes:u64 = num_likes.into() ;
-		Urls::<T>::insert(&url, (total_likes, &sender, Self::get_pot_id()));
-		log::debug!(target: "LIKE", "Creating first like record: {:?}", &sender);
+		let round_rewards = num_likes_to_balance::<T>(num_likes, SharePreviousLikers::get()) ;
 		let rounds: u32 = NumRoundsToRewardLikers::get().into() ;
-		Likes::<T>::insert(&sender, &url, (0, num_likes, num_likes*rounds, Self::get_pot_id()));
-		log::debug!(target: "LIKE", "Sending likes to pot: {:?}", num_likes);
-
-		// The share of previous likers goes to the pot
-		let previous_liker = Self::get_pot_id() ;
-		Self::pay_previous_liker(&sender, num_likes, &previous_liker) ;
-
-		// Pay author, referrer and extra
-		Self::pay_author_referrer_and_extra(&sender, url, url_ref, num_likes) ;
+		let rewards = round_rewards.saturating_mul(rounds.into()) ;
+		Urls::<T>::insert(&url, (total_likes, &sender, Self::get_pot_id(), BalanceOf::<T>::zero(), rewards));
+		log::debug!(target: "LIKE", "Creating first like record: {:?}", &sender);
+		Likes::<T>::insert(&sender, &url, (0, num_likes, rewards, Self::get_pot_id(), BalanceOf::<T>::zero(), round_rewards));
 
 		// Update total count of Urls
 		let url_count = UrlCount::take() + 1 ;
@@ -305,6 +713,58 @@ impl<T: Config> Module<T> {
 		log::debug!(target: "LIKE", "Updated url_count: {:?}", url_count);
 	}
 
+	// Upper bound of the weight of a batch of likes,
+	// each like may recycle the sender's record and archive MaxSkippedPerLike records
+	fn like_batch_weight(num_items: u32) -> Weight {
+		T::WeightInfo::like(1, T::MaxSkippedPerLike::get()).saturating_mul(num_items.into())
+	}
+
+	// Checks and processing of a like, shared by like and like_batch
+	// Returns the weight of the work done
+	fn do_like(sender: &T::AccountId, url: Vec<u8>, url_ref: Vec<u8>, num_likes: u32) -> Result<Weight, DispatchError> {
+		// Likes are recorded under the canonical URL
+		let url = canonicalize_url(&url) ;
+		let url_ref = canonicalize_url(&url_ref) ;
+
+		// Check that there's enough funds to pay for the likes
+		let total_price_u128 = num_likes_to_price::<T>(num_likes) ;
+		let total_price_balance = u128_to_balance::<T>(total_price_u128) ;
+		let free = T::Currency::free_balance(sender) ;
+		ensure!(free>=total_price_balance.saturating_add(T::Currency::minimum_balance()), Error::<T>::NotEnoughBalanceToLike) ;
+
+		// Check that num_likes is smaller than MaxLikes
+		let max_likes: u32 = MaxLikes::get() ;
+		ensure!(num_likes<=max_likes, Error::<T>::TooManyLikes) ;
+
+		// Check that URL is not too long
+		ensure!(url.len()<T::MaxLengthURL::get().into(), Error::<T>::UrlTooLong) ;
+
+		// A previous like of the sender must have been paid in full
+		let existing = Urls::<T>::contains_key(&url) ;
+		let max_steps = T::MaxSkippedPerLike::get() ;
+		let mut recycled: u32 = 0 ;
+		let mut steps: u32 = 0 ;
+		if existing && Likes::<T>::contains_key(sender, &url) {
+			steps = Self::recycle_like(sender, &url, max_steps)? ;
+			recycled = 1 ;
+		}
+
+		// The whole price goes to the pot in one transfer, the shares are then credited
+		Self::pay(sender, total_price_balance, &Self::get_pot_id())?;
+		Self::credit_author_and_referrer(&url, &url_ref, num_likes)?;
+
+		// Store the new like.
+		if existing {
+			steps += Self::like_existing_url(sender, &url, num_likes, max_steps-steps) ;
+		} else {
+			Self::like_new_url(sender, &url, num_likes) ;
+		}
+
+		// Emit an event that the like was processed.
+		Self::deposit_event(RawEvent::Liked(sender.clone(), url, num_likes));
+		Ok(T::WeightInfo::like(recycled, steps))
+	}
+
 }
 
 decl_module! {
@@ -316,35 +776,183 @@ decl_module! {
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
+        // Migrate storage if needed
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight: Weight = 0 ;
+            let version = StorageVersion::get() ;
+            if version<1 {
+                weight += Self::migrate_to_v1() ;
+            }
+            if version<2 {
+                weight += Self::migrate_to_v2() ;
+            }
+            if version<STORAGE_VERSION {
+                StorageVersion::set(STORAGE_VERSION) ;
+                weight += T::DbWeight::get().writes(1) ;
+            }
+            weight
+        }
+
         /// Create a new question
-        #[weight = 10_000]
-        fn like(origin, url: Vec<u8>, url_ref: Vec<u8>, num_likes: u32) {
+        #[weight = T::WeightInfo::like(1, T::MaxSkippedPerLike::get())]
+        #[transactional]
+        fn like(origin, url: Vec<u8>, url_ref: Vec<u8>, num_likes: u32) -> DispatchResultWithPostInfo {
             // Check that the extrinsic was signed and get the signer.
             let sender = ensure_signed(origin)?;
 
-			// Check that there's enough funds to pay for the likes
-			let total_price_u128 = num_likes_to_price::<T>(num_likes) ;
+            let weight = Self::do_like(&sender, url, url_ref, num_likes)?;
+            Ok(Some(weight).into())
+        }
+
+        /// Like several URLs at once, each item being (url, url_ref, num_likes)
+        /// Either all the likes are processed or none of them
+        #[weight = Module::<T>::like_batch_weight(likes.len().saturated_into())]
+        #[transactional]
+        fn like_batch(origin, likes: BoundedVec<(Vec<u8>, Vec<u8>, u32), T::MaxLikesPerBatch>) -> DispatchResultWithPostInfo {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+
+			// Check the size of the batch, MaxLikesPerBatch is enforced when decoding it
+			let num_items: u32 = likes.len().saturated_into() ;
+			ensure!(num_items>0, Error::<T>::InvalidBatchSize) ;
+
+			// Check that there's enough funds to pay for all the likes
+			let total_price_u128 = likes.iter()
+				.fold(0u128, |total, (_, _, num_likes)| total.saturating_add(num_likes_to_price::<T>(*num_likes))) ;
 			let total_price_balance = u128_to_balance::<T>(total_price_u128) ;
 			let free = T::Currency::free_balance(&sender) ;
-			ensure!(free>total_price_balance, Error::<T>::NotEnoughBalanceToLike) ;
+			ensure!(free>=total_price_balance.saturating_add(T::Currency::minimum_balance()), Error::<T>::NotEnoughBalanceToLike) ;
 
-			// Check that num_likes is smaller than MaxLikes
-			let max_likes: u32 = MaxLikes::get() ;
-			ensure!(num_likes<=max_likes, Error::<T>::TooManyLikes) ;
+			// Process each like, any failure reverts the whole batch
+			let mut weight: Weight = 0 ;
+			for (url, url_ref, num_likes) in likes.into_inner() {
+				weight = weight.saturating_add(Self::do_like(&sender, url, url_ref, num_likes)?) ;
+			}
+            Ok(Some(weight.min(Self::like_batch_weight(num_items))).into())
+        }
 
-			// Check that URL is not too long
-			ensure!(url.len()<T::MaxLengthURL::get().into(), Error::<T>::UrlTooLong) ;
+        /// Withdraw the rewards credited to the sender and the ones the lines of the given URLs paid for
+        #[weight = T::WeightInfo::claim_rewards(urls.len().saturated_into())]
+        fn claim_rewards(origin, urls: Vec<Vec<u8>>) -> DispatchResultWithPostInfo {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
 
-            // Store the new like.
-            if Urls::<T>::contains_key(&url) {
-            	ensure!(!Likes::<T>::contains_key(&sender, &url), Error::<T>::AlreadyInQueue);
-            	Self::like_existing_url(&sender, &url, &url_ref, num_likes) ;
-            } else {
-            	Self::like_new_url(&sender, &url, &url_ref, num_likes) ;
-            }
+			// Check the number of records to go through
+			let count: u32 = urls.len().saturated_into() ;
+			ensure!(count<=T::MaxClaimedLikes::get(), Error::<T>::InvalidClaimSize) ;
+
+			// Collect what the lines paid for, once per canonical URL
+			// - LikeOf: Record once its rewards are withdrawn
+			// - bool: Whether the line paid it in full and moved past it, so it gets archived
+			let mut settled: BTreeMap<Vec<u8>, (LikeOf<T>, bool)> = BTreeMap::new() ;
+			let pending = PendingRewards::<T>::get(&sender) ;
+			let mut total: BalanceOf<T> = pending ;
+			let mut visited: u32 = 0 ;
+			for url in urls {
+				let url = canonicalize_url(&url) ;
+				if settled.contains_key(&url) {
+					continue ;
+				}
+				visited += 1 ;
+				if !Likes::<T>::contains_key(&sender, &url) {
+					continue ;
+				}
+				let record = Likes::<T>::get(&sender, &url) ;
+				let (_, first_in_line, _, paid, _) = Urls::<T>::get(&url) ;
+				let due = Self::due_rewards(&record, paid) ;
+				let archived = due==record.2 && Self::is_behind_head(&url, &first_in_line, &record) ;
+				if due.is_zero() && !archived {
+					continue ;
+				}
+				total = total.saturating_add(due) ;
+				settled.insert(url, (Self::settle(&record, due), archived)) ;
+			}
+			// Records paid in full are archived even once their rewards were all withdrawn
+			let to_archive = settled.values().any(|(_, archived)| *archived) ;
+			ensure!(!total.is_zero() || to_archive, Error::<T>::NoRewardsToClaim) ;
+			let earned = total.saturating_sub(pending) ;
+
+			// The pot holds every share the lines were paid with
+			if !total.is_zero() {
+				T::Currency::transfer(&Self::get_pot_id(), &sender, total, ExistenceRequirement::AllowDeath)?;
+			}
+			if !pending.is_zero() {
+				PendingRewards::<T>::remove(&sender) ;
+			}
+			LikeSummary::<T>::mutate(&sender, |summary| summary.1 = summary.1.saturating_add(earned)) ;
+			for (url, (record, archived)) in settled {
+				if archived {
+					Self::archive_like(&sender, &url, &record) ;
+				} else {
+					Likes::<T>::insert(&sender, &url, record) ;
+				}
+			}
+
+            Self::deposit_event(RawEvent::RewardsClaimed(sender, total));
+            Ok(Some(T::WeightInfo::claim_rewards(visited)).into())
+        }
+
+        /// Update the price of 1 like
+        #[weight = 10_000]
+        fn set_like_price(origin, price: u128) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(Self::validate_like_price(price), Error::<T>::InvalidLikePrice) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			LikePrice::set(price) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        /// Update the maximum number of likes per transaction
+        #[weight = 10_000]
+        fn set_max_likes(origin, max_likes: u32) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(Self::validate_max_likes(max_likes), Error::<T>::InvalidMaxLikes) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			MaxLikes::set(max_likes) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        /// Update how likes are split amongst authors, referrers and previous likers
+        /// (the remainder goes to the pot)
+        #[weight = 10_000]
+        fn set_shares(origin, share_author: u8, share_referrer: u8, share_previous_likers: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check that the shares don't exceed 100%
+			ensure!(Self::validate_shares(share_author, share_referrer, share_previous_likers), Error::<T>::InvalidShares) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			ShareAuthor::set(share_author) ;
+			ShareReferrer::set(share_referrer) ;
+			SharePreviousLikers::set(share_previous_likers) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        /// Update the number of rounds during which likers receive rewards
+        #[weight = 10_000]
+        fn set_num_rounds(origin, num_rounds: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(num_rounds>0, Error::<T>::InvalidNumRounds) ;
 
-            // Emit an event that the like was processed.
-            Self::deposit_event(RawEvent::Liked(sender, url, num_likes));
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			NumRoundsToRewardLikers::set(num_rounds) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
         }
 
     }
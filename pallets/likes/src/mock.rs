@@ -0,0 +1,149 @@
+use crate as pallet_likes ;
+use frame_support::{parameter_types, traits::AllowAll} ;
+use sp_core::H256 ;
+use sp_runtime::{
+	BuildStorage,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup}
+} ;
+use sp_std::vec::Vec ;
+use wika_traits::OwnershipRegistry ;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test> ;
+type Block = frame_system::mocking::MockBlock<Test> ;
+
+pub const ALICE: u64 = 1 ;
+pub const BOB: u64 = 2 ;
+pub const CHARLIE: u64 = 3 ;
+pub const DAVE: u64 = 4 ;
+pub const AUTHOR: u64 = 10 ;
+pub const REFERRER: u64 = 11 ;
+pub const OWNERS_POT: u64 = 99 ;
+
+// Accounts 1 to NUM_FUNDED start with INITIAL_BALANCE
+pub const NUM_FUNDED: u64 = 8 ;
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000_000 ;
+
+// URLs owned by AUTHOR and REFERRER, in canonical form
+pub const AUTHOR_URL: &[u8] = b"https://author.example/post" ;
+pub const REFERRER_URL: &[u8] = b"https://referrer.example/page" ;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Likes: pallet_likes::{Pallet, Call, Storage, Config, Event<T>},
+	}
+) ;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250 ;
+	pub const SS58Prefix: u8 = 42 ;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = AllowAll ;
+	type BlockWeights = () ;
+	type BlockLength = () ;
+	type DbWeight = () ;
+	type Origin = Origin ;
+	type Call = Call ;
+	type Index = u64 ;
+	type BlockNumber = u64 ;
+	type Hash = H256 ;
+	type Hashing = BlakeTwo256 ;
+	type AccountId = u64 ;
+	type Lookup = IdentityLookup<Self::AccountId> ;
+	type Header = Header ;
+	type Event = Event ;
+	type BlockHashCount = BlockHashCount ;
+	type Version = () ;
+	type PalletInfo = PalletInfo ;
+	type AccountData = pallet_balances::AccountData<u128> ;
+	type OnNewAccount = () ;
+	type OnKilledAccount = () ;
+	type SystemWeightInfo = () ;
+	type SS58Prefix = SS58Prefix ;
+	type OnSetCode = () ;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1 ;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = () ;
+	type MaxReserves = () ;
+	type ReserveIdentifier = [u8; 8] ;
+	type Balance = u128 ;
+	type Event = Event ;
+	type DustRemoval = () ;
+	type ExistentialDeposit = ExistentialDeposit ;
+	type AccountStore = System ;
+	type WeightInfo = () ;
+}
+
+// AUTHOR_URL and REFERRER_URL are owned, the shares of other URLs go to the owners pot
+pub struct TestOwners ;
+
+impl OwnershipRegistry<Test> for TestOwners {
+	fn get_pot_id() -> u64 {
+		OWNERS_POT
+	}
+
+	fn get_owner(url: &Vec<u8>) -> u64 {
+		if url.as_slice()==AUTHOR_URL {
+			AUTHOR
+		} else if url.as_slice()==REFERRER_URL {
+			REFERRER
+		} else {
+			OWNERS_POT
+		}
+	}
+}
+
+parameter_types! {
+	pub const MaxLengthURL: u8 = 255 ;
+	pub const MaxClaimedLikes: u32 = 10 ;
+	pub const MaxSkippedPerLike: u32 = 5 ;
+	pub const MaxLikeHistory: u32 = 3 ;
+	pub const MaxLikesPerBatch: u32 = 5 ;
+}
+
+impl pallet_likes::Config for Test {
+	type Event = Event ;
+	type Currency = Balances ;
+	type MaxLengthURL = MaxLengthURL ;
+	type OwnershipRegistry = TestOwners ;
+	type MaxClaimedLikes = MaxClaimedLikes ;
+	type MaxSkippedPerLike = MaxSkippedPerLike ;
+	type MaxLikeHistory = MaxLikeHistory ;
+	type MaxLikesPerBatch = MaxLikesPerBatch ;
+	type WeightInfo = () ;
+}
+
+// Build genesis storage with the default likes parameters
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig {
+			balances: (1..=NUM_FUNDED).map(|account| (account, INITIAL_BALANCE)).collect(),
+		},
+		likes: LikesConfig {
+			like_price: 1_000_000_000_000,
+			max_likes: 100,
+			share_author: 33,
+			share_referrer: 33,
+			share_previous_likers: 33,
+			num_rounds_to_reward_likers: 4,
+		},
+	}.build_storage().unwrap() ;
+	let mut ext = sp_io::TestExternalities::new(storage) ;
+	// Events are only recorded from block 1
+	ext.execute_with(|| System::set_block_number(1)) ;
+	ext
+}
@@ -0,0 +1,464 @@
+use crate::{mock::*, Error, LikePrice, MaxLikes, ShareAuthor, ShareReferrer, SharePreviousLikers,
+	NumRoundsToRewardLikers, MAX_LIKE_PRICE, MAX_MAX_LIKES} ;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo, traits::OnRuntimeUpgrade,
+	BoundedVec, StorageValue} ;
+use sp_runtime::DispatchError ;
+use sp_std::convert::TryFrom ;
+use parity_scale_codec::{Decode, Encode} ;
+
+// Price of 1 like at genesis
+const PRICE: u128 = 1_000_000_000_000 ;
+
+// Value of 1 like at the genesis shares (33%)
+const SHARE: u128 = 330_000_000_000 ;
+
+// Unowned URL, its shares go to the owners pot
+fn url(i: u32) -> Vec<u8> {
+	format!("https://example.com/{}", i).into_bytes()
+}
+
+// Like a URL, referred by itself
+fn like(who: u64, url: &[u8], num_likes: u32) -> DispatchResultWithPostInfo {
+	Likes::like(Origin::signed(who), url.to_vec(), url.to_vec(), num_likes)
+}
+
+// Batch of likes of unowned URLs, each referred by itself
+fn batch(likes: &[(Vec<u8>, u32)]) -> BoundedVec<(Vec<u8>, Vec<u8>, u32), MaxLikesPerBatch> {
+	let likes: Vec<(Vec<u8>, Vec<u8>, u32)> = likes.iter()
+		.map(|(url, num_likes)| (url.clone(), url.clone(), *num_likes))
+		.collect() ;
+	BoundedVec::try_from(likes).expect("at most MaxLikesPerBatch likes")
+}
+
+fn claim(who: u64, urls: &[Vec<u8>]) -> DispatchResultWithPostInfo {
+	Likes::claim_rewards(Origin::signed(who), urls.to_vec())
+}
+
+// Last event deposited by the likes pallet
+fn last_event() -> crate::Event<Test> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			Event::Likes(event) => Some(event),
+			_ => None
+		})
+		.last()
+		.expect("a likes event was deposited")
+}
+
+#[test]
+fn set_like_price_works() {
+	new_test_ext().execute_with(|| {
+		let old = Likes::get_parameters() ;
+		assert_ok!(Likes::set_like_price(Origin::root(), 5)) ;
+		assert_eq!(LikePrice::get(), 5) ;
+		let new = (5, old.1, old.2, old.3, old.4, old.5) ;
+		assert_eq!(last_event(), crate::RawEvent::ParametersUpdated(old, new)) ;
+	}) ;
+}
+
+#[test]
+fn set_like_price_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_like_price(Origin::signed(ALICE), 5), DispatchError::BadOrigin) ;
+	}) ;
+}
+
+#[test]
+fn set_like_price_rejects_invalid_values() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_like_price(Origin::root(), 0), Error::<Test>::InvalidLikePrice) ;
+		assert_noop!(Likes::set_like_price(Origin::root(), MAX_LIKE_PRICE+1), Error::<Test>::InvalidLikePrice) ;
+		assert_ok!(Likes::set_like_price(Origin::root(), MAX_LIKE_PRICE)) ;
+	}) ;
+}
+
+#[test]
+fn set_max_likes_works() {
+	new_test_ext().execute_with(|| {
+		let old = Likes::get_parameters() ;
+		assert_ok!(Likes::set_max_likes(Origin::root(), 7)) ;
+		assert_eq!(MaxLikes::get(), 7) ;
+		let new = (old.0, 7, old.2, old.3, old.4, old.5) ;
+		assert_eq!(last_event(), crate::RawEvent::ParametersUpdated(old, new)) ;
+	}) ;
+}
+
+#[test]
+fn set_max_likes_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_max_likes(Origin::signed(ALICE), 7), DispatchError::BadOrigin) ;
+	}) ;
+}
+
+#[test]
+fn set_max_likes_rejects_invalid_values() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_max_likes(Origin::root(), 0), Error::<Test>::InvalidMaxLikes) ;
+		assert_noop!(Likes::set_max_likes(Origin::root(), MAX_MAX_LIKES+1), Error::<Test>::InvalidMaxLikes) ;
+		assert_ok!(Likes::set_max_likes(Origin::root(), MAX_MAX_LIKES)) ;
+	}) ;
+}
+
+#[test]
+fn set_shares_works() {
+	new_test_ext().execute_with(|| {
+		let old = Likes::get_parameters() ;
+		assert_ok!(Likes::set_shares(Origin::root(), 50, 20, 30)) ;
+		assert_eq!((ShareAuthor::get(), ShareReferrer::get(), SharePreviousLikers::get()), (50, 20, 30)) ;
+		let new = (old.0, old.1, 50, 20, 30, old.5) ;
+		assert_eq!(last_event(), crate::RawEvent::ParametersUpdated(old, new)) ;
+	}) ;
+}
+
+#[test]
+fn set_shares_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_shares(Origin::signed(ALICE), 50, 20, 30), DispatchError::BadOrigin) ;
+	}) ;
+}
+
+#[test]
+fn set_shares_rejects_more_than_100() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_shares(Origin::root(), 50, 30, 21), Error::<Test>::InvalidShares) ;
+		// Shares are added without overflowing u8
+		assert_noop!(Likes::set_shares(Origin::root(), 255, 255, 255), Error::<Test>::InvalidShares) ;
+	}) ;
+}
+
+#[test]
+fn set_num_rounds_works() {
+	new_test_ext().execute_with(|| {
+		let old = Likes::get_parameters() ;
+		assert_ok!(Likes::set_num_rounds(Origin::root(), 2)) ;
+		assert_eq!(NumRoundsToRewardLikers::get(), 2) ;
+		let new = (old.0, old.1, old.2, old.3, old.4, 2) ;
+		assert_eq!(last_event(), crate::RawEvent::ParametersUpdated(old, new)) ;
+	}) ;
+}
+
+#[test]
+fn set_num_rounds_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_num_rounds(Origin::signed(ALICE), 2), DispatchError::BadOrigin) ;
+	}) ;
+}
+
+#[test]
+fn set_num_rounds_rejects_zero() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::set_num_rounds(Origin::root(), 0), Error::<Test>::InvalidNumRounds) ;
+	}) ;
+}
+
+#[test]
+fn like_credits_author_and_referrer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Likes::like(Origin::signed(ALICE), AUTHOR_URL.to_vec(), REFERRER_URL.to_vec(), 2)) ;
+		assert_eq!(last_event(), crate::RawEvent::Liked(ALICE, AUTHOR_URL.to_vec(), 2)) ;
+
+		// The whole price goes to the pot, the shares are credited until claimed
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE-2*PRICE) ;
+		assert_eq!(Balances::free_balance(Likes::get_pot_id()), 2*PRICE) ;
+		assert_eq!(Likes::pending_rewards(AUTHOR), 2*SHARE) ;
+		assert_eq!(Likes::pending_rewards(REFERRER), 2*SHARE) ;
+		assert_eq!(Balances::free_balance(AUTHOR), 0) ;
+
+		// The liker's share rewards the line over NumRoundsToRewardLikers rounds
+		assert_eq!(Likes::get_like(&ALICE, &AUTHOR_URL.to_vec()), Some((0, 2, 8*SHARE, 4, 0, None))) ;
+		assert_eq!(Likes::get_url(&AUTHOR_URL.to_vec()), Some((2, ALICE, ALICE, 8*SHARE))) ;
+	}) ;
+}
+
+#[test]
+fn like_sends_unowned_shares_to_owners_pot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(1), 1)) ;
+		assert_eq!(Balances::free_balance(OWNERS_POT), 2*SHARE) ;
+		assert_eq!(Likes::pending_rewards(OWNERS_POT), 0) ;
+		assert_eq!(Balances::free_balance(Likes::get_pot_id()), PRICE-2*SHARE) ;
+	}) ;
+}
+
+#[test]
+fn like_pays_previous_likers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(1), 1)) ;
+		assert_ok!(like(BOB, &url(1), 1)) ;
+
+		// BOB's share paid one round of ALICE's rewards
+		assert_eq!(Likes::get_like(&ALICE, &url(1)), Some((0, 1, 4*SHARE, 4, SHARE, Some(BOB)))) ;
+		assert_eq!(Likes::get_like(&BOB, &url(1)), Some((1, 1, 4*SHARE, 4, 0, None))) ;
+		assert_eq!(Likes::get_url(&url(1)), Some((2, ALICE, BOB, 7*SHARE))) ;
+		assert_eq!(Likes::get_queue(&url(1)), vec![(ALICE, 3*SHARE), (BOB, 4*SHARE)]) ;
+	}) ;
+}
+
+#[test]
+fn claim_rewards_withdraws_pending_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Likes::like(Origin::signed(ALICE), AUTHOR_URL.to_vec(), REFERRER_URL.to_vec(), 2)) ;
+
+		assert_ok!(claim(AUTHOR, &[])) ;
+		assert_eq!(last_event(), crate::RawEvent::RewardsClaimed(AUTHOR, 2*SHARE)) ;
+		assert_eq!(Balances::free_balance(AUTHOR), 2*SHARE) ;
+		assert_eq!(Likes::pending_rewards(AUTHOR), 0) ;
+		assert_noop!(claim(AUTHOR, &[]), Error::<Test>::NoRewardsToClaim) ;
+
+		// The referrer's rewards are not affected
+		assert_eq!(Likes::pending_rewards(REFERRER), 2*SHARE) ;
+	}) ;
+}
+
+#[test]
+fn claim_rewards_across_urls() {
+	new_test_ext().execute_with(|| {
+		for i in 0..2 {
+			assert_ok!(like(ALICE, &url(i), 1)) ;
+			assert_ok!(like(BOB, &url(i), 1)) ;
+		}
+
+		// Each URL is counted once, whatever its form
+		let balance = Balances::free_balance(ALICE) ;
+		let mut duplicate = url(0) ;
+		duplicate.extend_from_slice(b"#top") ;
+		assert_ok!(claim(ALICE, &[url(0), url(1), duplicate])) ;
+		assert_eq!(last_event(), crate::RawEvent::RewardsClaimed(ALICE, 2*SHARE)) ;
+		assert_eq!(Balances::free_balance(ALICE), balance+2*SHARE) ;
+		assert_eq!(Likes::like_summary(ALICE), (0, 2*SHARE)) ;
+
+		// Claimed rewards leave the line, the rest is still owed
+		for i in 0..2 {
+			assert_eq!(Likes::get_like(&ALICE, &url(i)), Some((0, 1, 3*SHARE, 3, 0, Some(BOB)))) ;
+		}
+		assert_noop!(claim(ALICE, &[url(0), url(1)]), Error::<Test>::NoRewardsToClaim) ;
+	}) ;
+}
+
+#[test]
+fn claim_rewards_limits_urls_per_call() {
+	new_test_ext().execute_with(|| {
+		let urls: Vec<Vec<u8>> = (0..11).map(url).collect() ;
+		for url in &urls {
+			assert_ok!(like(ALICE, url, 1)) ;
+			assert_ok!(like(BOB, url, 1)) ;
+		}
+		assert_noop!(claim(ALICE, &urls), Error::<Test>::InvalidClaimSize) ;
+
+		// The URLs beyond MaxClaimedLikes are claimed in a later call
+		let balance = Balances::free_balance(ALICE) ;
+		assert_ok!(claim(ALICE, &urls[..10])) ;
+		assert_eq!(Balances::free_balance(ALICE), balance+10*SHARE) ;
+		assert_ok!(claim(ALICE, &urls[10..])) ;
+		assert_eq!(Balances::free_balance(ALICE), balance+11*SHARE) ;
+		assert_noop!(claim(ALICE, &urls[..10]), Error::<Test>::NoRewardsToClaim) ;
+	}) ;
+}
+
+#[test]
+fn claim_rewards_fails_without_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(claim(ALICE, &[]), Error::<Test>::NoRewardsToClaim) ;
+		assert_noop!(claim(ALICE, &[url(0)]), Error::<Test>::NoRewardsToClaim) ;
+
+		// The line has not paid anything yet
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+		assert_noop!(claim(ALICE, &[url(0)]), Error::<Test>::NoRewardsToClaim) ;
+	}) ;
+}
+
+#[test]
+fn like_again_while_in_line_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+		assert_ok!(like(BOB, &url(0), 1)) ;
+		assert_noop!(like(ALICE, &url(0), 1), Error::<Test>::AlreadyInQueue) ;
+	}) ;
+}
+
+#[test]
+fn like_again_once_paid_in_full() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+
+		// BOB's share pays all the rounds of ALICE, whose record is archived
+		assert_ok!(like(BOB, &url(0), 4)) ;
+		assert_eq!(Likes::get_like(&ALICE, &url(0)), None) ;
+		assert_eq!(Likes::pending_rewards(ALICE), 4*SHARE) ;
+		assert_eq!(Likes::like_summary(ALICE), (1, 4*SHARE)) ;
+		assert_eq!(Likes::get_like_history(&ALICE, &url(0)), vec![(0, 1, SHARE)]) ;
+
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+		assert_eq!(Likes::get_like(&ALICE, &url(0)), Some((5, 1, 4*SHARE, 4, 0, None))) ;
+		assert_eq!(Likes::get_queue(&url(0)), vec![(BOB, 15*SHARE), (ALICE, 4*SHARE)]) ;
+	}) ;
+}
+
+#[test]
+fn like_again_recycles_record_left_beyond_skip_limit() {
+	new_test_ext().execute_with(|| {
+		// Six likers in line, each owed 10 rounds
+		assert_ok!(Likes::set_num_rounds(Origin::root(), 10)) ;
+		for account in 1..=6 {
+			assert_ok!(like(account, &url(0), 1)) ;
+		}
+
+		// This pays the whole line, but only MaxSkippedPerLike likers are archived
+		assert_ok!(like(7, &url(0), 100)) ;
+		for account in 1..=5 {
+			assert_eq!(Likes::get_like(&account, &url(0)), None) ;
+		}
+		assert_eq!(Likes::get_like(&6, &url(0)), Some((5, 1, 10*SHARE, 10, 10*SHARE, None))) ;
+		assert_eq!(Likes::get_queue(&url(0)), vec![(7, 1000*SHARE)]) ;
+
+		// The record left behind is archived when its liker likes again
+		assert_ok!(like(6, &url(0), 1)) ;
+		assert_eq!(Likes::pending_rewards(6), 10*SHARE) ;
+		assert_eq!(Likes::like_summary(6), (1, 10*SHARE)) ;
+		assert_eq!(Likes::get_like_history(&6, &url(0)), vec![(5, 1, SHARE)]) ;
+		assert_eq!(Likes::get_like(&6, &url(0)), Some((106, 1, 10*SHARE, 10, 0, None))) ;
+		assert_eq!(Likes::get_queue(&url(0)), vec![(7, 999*SHARE), (6, 10*SHARE)]) ;
+	}) ;
+}
+
+#[test]
+fn like_history_keeps_latest_rounds() {
+	new_test_ext().execute_with(|| {
+		// With a single round, each like of BOB pays the previous like of ALICE in full
+		assert_ok!(Likes::set_num_rounds(Origin::root(), 1)) ;
+		for num_likes in 1..=4 {
+			assert_ok!(like(ALICE, &url(0), num_likes)) ;
+			assert_ok!(like(BOB, &url(0), num_likes)) ;
+		}
+
+		// Only MaxLikeHistory rounds are kept, LikeSummary counts them all
+		assert_eq!(Likes::get_like_history(&ALICE, &url(0)),
+			vec![(2, 2, 2*SHARE), (6, 3, 3*SHARE), (12, 4, 4*SHARE)]) ;
+		assert_eq!(Likes::like_summary(ALICE), (10, 10*SHARE)) ;
+		assert_eq!(Likes::pending_rewards(ALICE), 10*SHARE) ;
+	}) ;
+}
+
+#[test]
+fn archived_record_leaves_queue_and_is_claimed_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+		assert_ok!(like(BOB, &url(0), 1)) ;
+		let balance = Balances::free_balance(ALICE) ;
+		assert_ok!(claim(ALICE, &[url(0)])) ;
+
+		// CHARLIE's share pays the rest of ALICE's rounds, her record is archived
+		assert_ok!(like(CHARLIE, &url(0), 3)) ;
+		assert_eq!(Likes::get_like(&ALICE, &url(0)), None) ;
+		assert_eq!(Likes::get_queue(&url(0)), vec![(BOB, 4*SHARE), (CHARLIE, 12*SHARE)]) ;
+		assert_eq!(Likes::pending_rewards(ALICE), 3*SHARE) ;
+
+		// Only the archived rewards are left to claim
+		assert_ok!(claim(ALICE, &[url(0)])) ;
+		assert_eq!(last_event(), crate::RawEvent::RewardsClaimed(ALICE, 3*SHARE)) ;
+		assert_eq!(Balances::free_balance(ALICE), balance+4*SHARE) ;
+		assert_eq!(Likes::like_summary(ALICE), (1, 4*SHARE)) ;
+		assert_noop!(claim(ALICE, &[url(0)]), Error::<Test>::NoRewardsToClaim) ;
+	}) ;
+}
+
+#[test]
+fn claim_rewards_archives_record_paid_in_full() {
+	new_test_ext().execute_with(|| {
+		// As in like_again_recycles_record_left_beyond_skip_limit, the line leaves 6 behind
+		assert_ok!(Likes::set_num_rounds(Origin::root(), 10)) ;
+		for account in 1..=6 {
+			assert_ok!(like(account, &url(0), 1)) ;
+		}
+		assert_ok!(like(7, &url(0), 100)) ;
+
+		let balance = Balances::free_balance(6) ;
+		assert_ok!(claim(6, &[url(0)])) ;
+		assert_eq!(Balances::free_balance(6), balance+10*SHARE) ;
+		assert_eq!(Likes::get_like(&6, &url(0)), None) ;
+		assert_eq!(Likes::pending_rewards(6), 0) ;
+		assert_eq!(Likes::like_summary(6), (1, 10*SHARE)) ;
+		assert_eq!(Likes::get_like_history(&6, &url(0)), vec![(5, 1, SHARE)]) ;
+		assert_noop!(claim(6, &[url(0)]), Error::<Test>::NoRewardsToClaim) ;
+	}) ;
+}
+
+#[test]
+fn migrate_to_v2_values_records_in_line_order() {
+	new_test_ext().execute_with(|| {
+		let pot = Likes::get_pot_id() ;
+		let url = url(0) ;
+
+		// v1 layout: CHARLIE was paid and left the line, ALICE and BOB are still in line
+		let charlie: crate::v0::LikeOf<Test> = (0, 1, 0, pot) ;
+		let alice: crate::v0::LikeOf<Test> = (1, 2, 5, BOB) ;
+		let bob: crate::v0::LikeOf<Test> = (3, 1, 4, pot) ;
+		crate::v0::Likes::<Test>::insert(CHARLIE, &url, charlie) ;
+		crate::v0::Likes::<Test>::insert(ALICE, &url, alice) ;
+		crate::v0::Likes::<Test>::insert(BOB, &url, bob) ;
+		crate::v0::Urls::<Test>::insert(&url, (4u64, ALICE, BOB)) ;
+		crate::StorageVersion::put(1) ;
+
+		Likes::on_runtime_upgrade() ;
+		assert_eq!(crate::StorageVersion::get(), 2) ;
+
+		// Remaining likes are valued at the current price and share
+		assert_eq!(Likes::get_like(&ALICE, &url), Some((1, 2, 5*SHARE, 3, 0, Some(BOB)))) ;
+		assert_eq!(Likes::get_like(&BOB, &url), Some((3, 1, 4*SHARE, 4, 0, None))) ;
+		assert_eq!(Likes::get_url(&url), Some((4, ALICE, BOB, 9*SHARE))) ;
+		assert_eq!(Likes::get_queue(&url), vec![(ALICE, 5*SHARE), (BOB, 4*SHARE)]) ;
+
+		// Records out of the line are archived
+		assert_eq!(Likes::get_like(&CHARLIE, &url), None) ;
+		assert_eq!(Likes::like_summary(CHARLIE), (1, 0)) ;
+
+		// The line pays the records in order
+		assert_ok!(like(DAVE, &url, 1)) ;
+		assert_eq!(Likes::get_like(&ALICE, &url), Some((1, 2, 5*SHARE, 3, SHARE, Some(BOB)))) ;
+		assert_eq!(Likes::get_like(&BOB, &url), Some((3, 1, 4*SHARE, 4, 0, Some(DAVE)))) ;
+	}) ;
+}
+
+#[test]
+fn like_batch_processes_every_like() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Likes::like_batch(Origin::signed(ALICE), batch(&[(url(0), 1), (url(1), 2)]))) ;
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE-3*PRICE) ;
+		assert_eq!(Likes::get_like(&ALICE, &url(0)), Some((0, 1, 4*SHARE, 4, 0, None))) ;
+		assert_eq!(Likes::get_like(&ALICE, &url(1)), Some((0, 2, 8*SHARE, 4, 0, None))) ;
+		assert_eq!(last_event(), crate::RawEvent::Liked(ALICE, url(1), 2)) ;
+	}) ;
+}
+
+#[test]
+fn like_batch_reverts_every_like_on_failure() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(like(ALICE, &url(0), 1)) ;
+		assert_ok!(like(BOB, &url(0), 1)) ;
+
+		// The like of url(1) is reverted with the failing one
+		assert_noop!(Likes::like_batch(Origin::signed(ALICE), batch(&[(url(1), 1), (url(0), 1)])),
+			Error::<Test>::AlreadyInQueue) ;
+		assert_noop!(Likes::like_batch(Origin::signed(ALICE), batch(&[(url(1), 1), (url(2), 101)])),
+			Error::<Test>::TooManyLikes) ;
+		assert_eq!(Likes::get_url(&url(1)), None) ;
+	}) ;
+}
+
+#[test]
+fn like_batch_size_is_limited() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Likes::like_batch(Origin::signed(ALICE), batch(&[])), Error::<Test>::InvalidBatchSize) ;
+
+		// Batches beyond MaxLikesPerBatch can not be built nor decoded
+		let likes: Vec<(Vec<u8>, u32)> = (0..6).map(|i| (url(i), 1)).collect() ;
+		let items: Vec<(Vec<u8>, Vec<u8>, u32)> = likes.iter()
+			.map(|(url, num_likes)| (url.clone(), url.clone(), *num_likes))
+			.collect() ;
+		assert!(BoundedVec::<(Vec<u8>, Vec<u8>, u32), MaxLikesPerBatch>::try_from(items.clone()).is_err()) ;
+		assert!(BoundedVec::<(Vec<u8>, Vec<u8>, u32), MaxLikesPerBatch>::decode(&mut &items.encode()[..]).is_err()) ;
+
+		assert_ok!(Likes::like_batch(Origin::signed(ALICE), batch(&likes[..5]))) ;
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE-5*PRICE) ;
+	}) ;
+}
@@ -0,0 +1,65 @@
+//! Weights for pallet_likes
+//! These are hand estimates, not benchmark results: the execution times are guesses and the
+//! database accesses are counted from the code. like_batch has no weight function of its own,
+//! it is charged like(1, MaxSkippedPerLike) per item. Replace this file with the output of:
+//! ./target/release/wika-node benchmark --chain dev --pallet pallet_likes --extrinsic '*'
+//!     --steps 50 --repeat 20 --output pallets/likes/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_likes.
+pub trait WeightInfo {
+	fn like(r: u32, s: u32, ) -> Weight;
+	fn claim_rewards(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_likes using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn like(r: u32, s: u32, ) -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((20_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
+	}
+	fn claim_rewards(n: u32, ) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn like(r: u32, s: u32, ) -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((20_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
+	}
+	fn claim_rewards(n: u32, ) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -0,0 +1,108 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_std::vec::Vec;
+
+
+
+/// Phase of a pending URL check.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RequestPhase {
+	/// Verifiers are sending their commits.
+	Commit,
+	/// Verifiers are sending their reveals.
+	Reveal,
+	/// Votes were aggregated, the request is waiting to be deleted.
+	Aggregated,
+}
+
+/// State of a verification result.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ResultState {
+	/// Verifiers reached quorum and agreement.
+	Decided,
+	/// Not enough reveals or no agreement, nothing was decided.
+	Inconclusive,
+}
+
+/// State of a pending URL check.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RequestInfo<AccountId, BlockNumber> {
+	/// Block at which the check was requested.
+	pub requested_at: BlockNumber,
+	/// Account that requested the check.
+	pub requester: AccountId,
+	/// Current phase.
+	pub phase: RequestPhase,
+	/// Blocks remaining until the end of the current phase.
+	pub blocks_remaining: BlockNumber,
+}
+
+/// Verification result of a URL.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ResultInfo<BlockNumber> {
+	/// Block at which the votes were aggregated.
+	pub block: BlockNumber,
+	/// Number of votes.
+	pub num_votes: u32,
+	/// Number of YES votes.
+	pub num_votes_yes: u32,
+	/// Number of votes in the majority.
+	pub num_votes_majority: u32,
+	/// First characters of the webpage.
+	#[cfg_attr(feature = "std", serde(with = "text"))]
+	pub intro: Vec<u8>,
+	/// Mark found on the page.
+	#[cfg_attr(feature = "std", serde(with = "text"))]
+	pub proof: Vec<u8>,
+	/// Whether the ownership was approved.
+	pub approved: bool,
+	/// Whether a decision was reached.
+	pub state: ResultState,
+}
+
+/// Serialize raw page bytes as text.
+#[cfg(feature = "std")]
+mod text {
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(bytes: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&String::from_utf8_lossy(bytes))
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+		Ok(String::deserialize(deserializer)?.into_bytes())
+	}
+}
+
+
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the owners pallet.
+	pub trait OwnersApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Owner of a URL, if any.
+		fn get_owner(url: Vec<u8>) -> Option<AccountId> ;
+
+		/// State of a pending URL check.
+		fn get_request(url: Vec<u8>) -> Option<RequestInfo<AccountId, BlockNumber>> ;
+
+		/// Verification result of a URL.
+		fn get_result(url: Vec<u8>) -> Option<ResultInfo<BlockNumber>> ;
+
+		/// URLs owned by an account.
+		fn get_owned_urls(account: AccountId) -> Vec<Vec<u8>> ;
+	}
+}
@@ -0,0 +1,98 @@
+//! RPC methods to query the owners pallet.
+
+use std::sync::Arc;
+
+use parity_scale_codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_owners_rpc_runtime_api::{
+	OwnersApi as OwnersRuntimeApi,
+	RequestPhase, RequestInfo, ResultState, ResultInfo
+};
+
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+
+/// Owners RPC methods.
+#[rpc]
+pub trait OwnersApi<BlockHash, AccountId, BlockNumber> {
+	/// Owner of a URL, if any.
+	#[rpc(name = "owners_getOwner")]
+	fn get_owner(&self, url: String, at: Option<BlockHash>) -> Result<Option<AccountId>>;
+
+	/// State of a pending URL check.
+	#[rpc(name = "owners_getRequest")]
+	fn get_request(&self, url: String, at: Option<BlockHash>) -> Result<Option<RequestInfo<AccountId, BlockNumber>>>;
+
+	/// Verification result of a URL.
+	#[rpc(name = "owners_getResult")]
+	fn get_result(&self, url: String, at: Option<BlockHash>) -> Result<Option<ResultInfo<BlockNumber>>>;
+
+	/// URLs owned by an account.
+	#[rpc(name = "owners_getOwnedUrls")]
+	fn get_owned_urls(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<String>>;
+}
+
+
+/// Implementation of the owners RPC methods.
+pub struct Owners<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Owners<C, B> {
+	/// Create a new instance from a client.
+	pub fn new(client: Arc<C>) -> Self {
+		Owners { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query the owners pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber> OwnersApi<<Block as BlockT>::Hash, AccountId, BlockNumber> for Owners<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: OwnersRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+{
+	fn get_owner(&self, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_owner(&at, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_request(&self, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<RequestInfo<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_request(&at, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_result(&self, url: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<ResultInfo<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.get_result(&at, url.into_bytes()).map_err(runtime_error)
+	}
+
+	fn get_owned_urls(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<String>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let urls = api.get_owned_urls(&at, account).map_err(runtime_error)?;
+		Ok(urls.into_iter().map(|url| String::from_utf8_lossy(&url).into_owned()).collect())
+	}
+}
@@ -6,6 +6,8 @@
 // -------------------------------------------------
 
 use sp_std::{
+	cmp::max,
+	string::String,
 	vec::Vec,
 	convert::TryInto,
 	collections::btree_map::BTreeMap
@@ -14,8 +16,10 @@ use sp_std::{
 use frame_support::{
 	ensure,
 	decl_error, decl_event, decl_module, decl_storage,
-	traits::{Currency, ExistenceRequirement, Get},
+	dispatch::DispatchResult,
+	traits::{Currency, ReservableCurrency, BalanceStatus, ExistenceRequirement, Get},
 	weights::Weight,
+	transactional,
 	PalletId
 };
 
@@ -33,7 +37,9 @@ use sp_runtime::{
 	SaturatedConversion,
 	RuntimeAppPublic,
 	traits::{
-		AccountIdConversion
+		AccountIdConversion,
+		Saturating,
+		Zero
 	},
 	offchain as rt_offchain,
 	offchain::{
@@ -52,13 +58,21 @@ use sp_io::hashing::{
 
 use parity_scale_codec::{Encode,Decode};
 
+use scale_info::TypeInfo ;
+
 use core::fmt::Debug ;
 
 use hex ;
 
 use numtoa::NumToA ;
 
-use wika_traits::OwnershipRegistry ;
+use wika_traits::{OwnershipRegistry, canonicalize_url} ;
+
+#[cfg(test)]
+mod mock ;
+
+#[cfg(test)]
+mod tests ;
 
 
 
@@ -102,15 +116,96 @@ pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>>  {
 	type OwnersPublic: RuntimeAppPublic + Debug + AsRef<[u8]> ;
 	type Call: From<Call<Self>>;
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-	type Currency: Currency<Self::AccountId> ;
+	type Currency: ReservableCurrency<Self::AccountId> ;
 	type MaxLengthURL: Get<u8> ;
 	type NumChecksRequired: Get<u8> ;
+	type VoteWeighting: VoteWeighting<Self> ;
+}
+
+// Weight given to each verifier's vote when aggregating a request
+pub trait VoteWeighting<T:Config> {
+
+	fn weight(verifier: &T::AccountId) -> u128 ;
+
+}
+
+// Every vote counts the same
+pub struct EqualWeight ;
+
+impl<T:Config> VoteWeighting<T> for EqualWeight {
+
+	fn weight(_verifier: &T::AccountId) -> u128 {
+		1
+	}
+
+}
+
+// Votes are weighted by the verifier's bond
+// Verifiers added by sudo without a bond weigh as much as a fresh bond
+pub struct StakeWeight ;
+
+impl<T:Config> VoteWeighting<T> for StakeWeight {
+
+	fn weight(verifier: &T::AccountId) -> u128 {
+		if VerifierBonds::<T>::contains_key(verifier) {
+			VerifierBonds::<T>::get(verifier).saturated_into()
+		} else {
+			VerifierBond::get()
+		}
+	}
+
+}
+
+// Votes are weighted by the number of correct votes scaled by accuracy
+// (votes_correct * votes_correct / votes_valid), plus one so fresh verifiers still count
+pub struct ReputationWeight ;
+
+impl<T:Config> VoteWeighting<T> for ReputationWeight {
+
+	fn weight(verifier: &T::AccountId) -> u128 {
+		let stats = Verifiers::<T>::get(verifier) ;
+		let valid: u128 = stats.6.into() ;
+		let correct: u128 = stats.8.into() ;
+		if valid==0 {
+			1
+		} else {
+			1 + correct*correct/valid
+		}
+	}
+
 }
 
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance ;
 
 type PublicOf<T> = <T as Config>::OwnersPublic ;
 
+// Request data, see Requests storage for details
+type RequestOf<T> = (<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::AccountId, u8, u8, u8) ;
+
+// Snapshot of the verification parameters, used to report changes
+// - u128: RequestPrice
+// - u8: NumBlocksToCommit
+// - u8: NumBlocksToReveal
+// - u8: NumBlocksToDelete
+// - u8: PrctNeededForAgreement
+// - u32: MajorityMin
+// - u32: OwnershipTtl
+// - u8: RenewPricePrct
+// - u128: ChallengeBond
+// - u8: VerifiersSharePrct
+// - u8: MaxRetries
+// - u8: NoRevealRefundPrct
+// - u8: InconclusiveRefundPrct
+pub type OwnersParameters = (u128, u8, u8, u8, u8, u32, u32, u8, u128, u8, u8, u8, u8) ;
+
+// Snapshot of the verifier staking parameters, used to report changes
+// - u128: VerifierBond
+// - u32: UnbondingPeriod
+// - u8: SlashPrct
+// - u8: MaxMinorityPrct
+// - u8: MaxMissedRevealsPrct
+pub type StakingParameters = (u128, u32, u8, u8, u8) ;
+
 const PALLET_ID: PalletId = PalletId(*b"AUTHORS!");
 
 const HASH_LENGTH: usize = 32 ;
@@ -123,14 +218,70 @@ const FETCH_TIMEOUT_PERIOD: u64 = 15000 ;
 
 const MARK_PREFIX: &str  = "wika.network/author/" ;
 
+// Name attribute of the meta tag, with double or single quotes
+const META_NAMES: [&str; 2] = ["name=\"wika-author\"", "name='wika-author'"] ;
+
+const WELL_KNOWN_PATH: &[u8] = b"/.well-known/wika.json" ;
+
+const WELL_KNOWN_KEY: &str = "\"author\"" ;
+
+const DNS_TXT_PREFIX: &str = "wika-author=" ;
+
+const PREFIX_WILDCARD: &[u8] = b"/*" ;
+
+// DNS-over-HTTPS resolver used when none is set in the offchain local storage
+const DOH_URL: &[u8] = b"https://cloudflare-dns.com/dns-query?type=TXT&name=" ;
+
+// Offchain local storage key of the DNS-over-HTTPS resolver, set by the node operator
+// The value is the raw resolver URL, the host is appended to it
+const DOH_URL_KEY: &[u8] = b"ownr/doh" ;
+
+const JSON_ACCEPT_HEADER: &str = "application/json" ;
+
+const DNS_ACCEPT_HEADER: &str = "application/dns-json" ;
+
 const REVEAL_QUEUE_PREFIX: &[u8] = b"ownr/r/";
 
 const USER_AGENT: &str  = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/93.0.4577.82 Safari/537.36" ;
 
 const ACCEPT_HEADER: &str = "text/html,application/xhtml+xml,application/xml" ;
 
+// Commits needed before the missed reveals rate can disable a verifier
+const MIN_COMMITS_FOR_MISS_RATE: u32 = 10 ;
+
 //const OFFCHAIN_CACHE_LOCK_TIMEOUT_MS: u64 = 250 ;
 
+const STORAGE_VERSION: u8 = 4 ;
+
+// Where verifiers look for the proof of ownership
+// - Body: MARK_PREFIX followed by the address anywhere in the page
+// - MetaTag: <meta name="wika-author" content="address"> in the page
+// - WellKnown: {"author": "address"} in /.well-known/wika.json on the same host
+// - DnsTxt: a "wika-author=address" TXT record on the host, resolved over DNS-over-HTTPS
+//   (see DOH_URL_KEY to choose the resolver)
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub enum ProofMethod {
+	Body,
+	MetaTag,
+	WellKnown,
+	DnsTxt,
+}
+
+impl Default for ProofMethod {
+	fn default() -> Self {
+		ProofMethod::Body
+	}
+}
+
+// Phases of a request, as reported to the runtime API
+pub const REQUEST_PHASE_COMMIT: u8 = 0 ;
+pub const REQUEST_PHASE_REVEAL: u8 = 1 ;
+pub const REQUEST_PHASE_AGGREGATED: u8 = 2 ;
+
+// States of a verification result
+pub const RESULT_STATE_DECIDED: u8 = 0 ;
+pub const RESULT_STATE_INCONCLUSIVE: u8 = 1 ;
+
 
 
 
@@ -145,6 +296,10 @@ fn u8_to_block<T:Config>(input: u8) -> T::BlockNumber {
 	input.saturated_into()
 }
 
+fn u32_to_block<T:Config>(input: u32) -> T::BlockNumber {
+	input.saturated_into()
+}
+
 fn block_to_u32<T:Config>(input: T::BlockNumber) -> u32 {
 	match input.try_into() {
 		Ok(num) => num,
@@ -152,14 +307,22 @@ fn block_to_u32<T:Config>(input: T::BlockNumber) -> u32 {
 	}
 }
 
-fn find_majority<K,V: Ord>(list: &Vec<(K, V)>) -> Option<(&V,u32)> {
+// Number of keys removed from a storage prefix
+fn num_removed(result: sp_io::KillStorageResult) -> Weight {
+	match result {
+		sp_io::KillStorageResult::AllRemoved(count) => count.into(),
+		sp_io::KillStorageResult::SomeRemaining(count) => count.into()
+	}
+}
+
+fn find_majority<K,V: Ord,F: Fn(&K) -> u128>(list: &Vec<(K, V)>, weight: F) -> Option<(&V,u128)> {
 	let mut map = BTreeMap::new() ;
-	for (_, v) in list {
-		let count: Option<&u32> = map.get(&v) ;
+	for (k, v) in list {
+		let count: Option<&u128> = map.get(&v) ;
 		if count.is_none() {
-			map.insert(v, 1) ;
+			map.insert(v, weight(k)) ;
 		} else {
-			let update = count.unwrap() + 1 ;
+			let update = count.unwrap() + weight(k) ;
 			map.insert(v, update) ;
 		}
 	}
@@ -178,13 +341,202 @@ fn find_majority<K,V: Ord>(list: &Vec<(K, V)>) -> Option<(&V,u32)> {
 
 
 
+// Hex encoding of an account, as it appears in ownership marks
+fn account_to_hex<A: Encode>(account: &A) -> Option<[u8; 64]> {
+	let address: [u8; 32] = account.encode().try_into().ok()? ;
+	let mut address_hex: [u8; 64] = [0; 64] ;
+	hex::encode_to_slice(address, &mut address_hex).ok()? ;
+	Some(address_hex)
+}
+
+fn find_bytes(data: &[u8], pattern: &[u8]) -> Option<usize> {
+	if pattern.is_empty() || pattern.len()>data.len() {
+		return None ;
+	}
+	data.windows(pattern.len()).position(|window| window==pattern)
+}
+
+// Check that a proof is exactly a prefix followed by the hex-encoded address of an account,
+// hex digits in any case
+fn prefixed_address_matches<A: Encode>(proof: &[u8], prefix: &[u8], account: &A) -> bool {
+	if !proof.starts_with(prefix) {
+		return false ;
+	}
+	match account_to_hex(account) {
+		Some(address_hex) => proof[prefix.len()..].eq_ignore_ascii_case(&address_hex),
+		None => false
+	}
+}
+
+// Check that a mark found on a page proves ownership by an account:
+// it must be MARK_PREFIX followed by the hex-encoded address and nothing else
+// Used by the offchain verifier and enforced on-chain when revealing
+fn mark_matches_account<A: Encode>(mark: &[u8], account: &A) -> bool {
+	prefixed_address_matches(mark, MARK_PREFIX.as_bytes(), account)
+}
+
+// Check that a proof revealed for a proof method is the account address
+fn proof_matches_account<A: Encode>(proof_method: ProofMethod, proof: &[u8], account: &A) -> bool {
+	match proof_method {
+		ProofMethod::Body => mark_matches_account(proof, account),
+		ProofMethod::DnsTxt => prefixed_address_matches(proof, DNS_TXT_PREFIX.as_bytes(), account),
+		ProofMethod::MetaTag | ProofMethod::WellKnown => prefixed_address_matches(proof, b"", account)
+	}
+}
+
+// Scheme and host of a URL, eg. https://example.com
+fn url_origin(url: &[u8]) -> Option<&[u8]> {
+	let host_idx = find_bytes(url, b"://")? + 3 ;
+	let host_len = url[host_idx..]
+		.iter()
+		.position(|c| *c==b'/' || *c==b'?' || *c==b'#')
+		.unwrap_or(url.len()-host_idx) ;
+	if host_len==0 {
+		return None ;
+	}
+	Some(&url[..host_idx+host_len])
+}
+
+// Host of a URL without the port, eg. example.com
+fn url_host(url: &[u8]) -> Option<&[u8]> {
+	let origin = url_origin(url)? ;
+	let host = &origin[find_bytes(origin, b"://")?+3..] ;
+	let host_len = host.iter().position(|c| *c==b':').unwrap_or(host.len()) ;
+	Some(&host[..host_len])
+}
+
+// Prefix ownership records end with /* and cover every URL under them,
+// eg. https://example.com/* or https://example.com/blog/*
+fn is_url_prefix(url: &[u8]) -> bool {
+	url.ends_with(PREFIX_WILDCARD)
+}
+
+// Prefix records that could cover a URL, from the most specific to the host
+fn url_prefixes(url: &[u8]) -> Vec<Vec<u8>> {
+	let mut prefixes: Vec<Vec<u8>> = sp_std::vec![] ;
+	let origin_len = match url_origin(url) {
+		Some(origin) => origin.len(),
+		None => return prefixes
+	} ;
+	let path_end = url[origin_len..]
+		.iter()
+		.position(|c| *c==b'?' || *c==b'#')
+		.map_or(url.len(), |idx| origin_len+idx) ;
+	for idx in (origin_len..path_end).rev() {
+		if url[idx]==b'/' {
+			let mut prefix: Vec<u8> = url[..idx+1].into() ;
+			prefix.push(b'*') ;
+			prefixes.push(prefix) ;
+		}
+	}
+	if path_end==origin_len {
+		let mut prefix: Vec<u8> = url[..origin_len].into() ;
+		prefix.extend_from_slice(PREFIX_WILDCARD) ;
+		prefixes.push(prefix) ;
+	}
+	prefixes
+}
+
+// Document to fetch to check a proof method
+// DNS records are fetched from the resolver, followed by the host
+fn proof_location(url: &Vec<u8>, proof_method: ProofMethod, resolver: &[u8]) -> Option<(Vec<u8>, &'static str)> {
+	match proof_method {
+		ProofMethod::Body | ProofMethod::MetaTag => Some((url.clone(), ACCEPT_HEADER)),
+		ProofMethod::WellKnown => {
+			let mut location: Vec<u8> = url_origin(url)?.into() ;
+			location.extend_from_slice(WELL_KNOWN_PATH) ;
+			Some((location, JSON_ACCEPT_HEADER))
+		},
+		ProofMethod::DnsTxt => {
+			let mut location: Vec<u8> = resolver.into() ;
+			location.extend_from_slice(url_host(url)?) ;
+			Some((location, DNS_ACCEPT_HEADER))
+		}
+	}
+}
+
+// Text between the first pair of double or single quotes following a key,
+// eg. content="..." or content='...' in a tag or "author": "..." in a JSON document
+fn quoted_value_after<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+	let rest = &text[text.find(key)?+key.len()..] ;
+	let quote_idx = rest.find(|c: char| c=='"' || c=='\'')? ;
+	let quote = &rest[quote_idx..quote_idx+1] ;
+	let start = quote_idx + 1 ;
+	let len = rest[start..].find(quote)? ;
+	Some(&rest[start..start+len])
+}
+
+// Content of the wika-author meta tag, whatever the order of the attributes and the quotes
+fn extract_meta_author(data: &str) -> Option<&str> {
+	let name_idx = META_NAMES.iter().find_map(|name| data.find(name))? ;
+	let tag_start = data[..name_idx].rfind('<')? ;
+	let tag_end = name_idx + data[name_idx..].find('>')? ;
+	quoted_value_after(&data[tag_start..tag_end], "content=")
+}
+
+// TXT record starting with DNS_TXT_PREFIX in a DNS-over-HTTPS JSON answer
+fn extract_dns_author(data: &str) -> Option<&str> {
+	let start = data.find(DNS_TXT_PREFIX)? ;
+	let len = data[start..].find(|c: char| c=='"' || c=='\\').unwrap_or(data.len()-start) ;
+	Some(&data[start..start+len])
+}
+
+// Intro and proof found in the document fetched for a proof method
+fn extract_proof(proof_method: ProofMethod, bytes: &[u8]) -> (Vec<u8>, Option<Vec<u8>>) {
+	if proof_method==ProofMethod::Body {
+		return extract_page_parts(bytes) ;
+	}
+	let data = String::from_utf8_lossy(bytes) ;
+	let intro: Vec<u8> = truncate_str(&data, INTRO_LENGTH).into() ;
+	let proof = match proof_method {
+		ProofMethod::MetaTag => extract_meta_author(&data),
+		ProofMethod::WellKnown => quoted_value_after(&data, WELL_KNOWN_KEY),
+		_ => extract_dns_author(&data)
+	} ;
+	let proof: Option<Vec<u8>> = proof.map(|x| truncate_str(x, MARK_LENGTH).into()) ;
+	(intro, proof)
+}
+
+// Longest prefix of a text that fits in max_len bytes and ends on a char boundary
+fn truncate_str(text: &str, max_len: usize) -> &str {
+	if text.len()<=max_len {
+		return text ;
+	}
+	let mut end = max_len ;
+	while !text.is_char_boundary(end) {
+		end -= 1 ;
+	}
+	&text[..end]
+}
+
+// Split a webpage into its intro and the mark used as proof of ownership
+// The mark is MARK_PREFIX followed by the alphanumeric characters of the address,
+// wherever it appears in the markup. Invalid UTF-8 is replaced, long intros and marks are truncated
+fn extract_page_parts(bytes: &[u8]) -> (Vec<u8>, Option<Vec<u8>>) {
+	let data = String::from_utf8_lossy(bytes) ;
+	let intro: Vec<u8> = truncate_str(&data, INTRO_LENGTH).into() ;
+	let mark: Option<Vec<u8>> = data
+		.find(MARK_PREFIX)
+		.map(|mark_idx| {
+			let address = &data[mark_idx+MARK_PREFIX.len()..] ;
+			let address_len = address.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(address.len()) ;
+			truncate_str(&data[mark_idx..mark_idx+MARK_PREFIX.len()+address_len], MARK_LENGTH).into()
+		}) ;
+	(intro, mark)
+}
 
 
 
 // Functions to fetch the data from URL
 // -------------------------------------------------
 
-fn fetch_from_url(url: &Vec<u8>) -> Option<Vec<u8>> {
+// DNS-over-HTTPS resolver set by the node operator, or DOH_URL
+fn doh_resolver() -> Vec<u8> {
+	sp_io::offchain::local_storage_get(rt_offchain::StorageKind::PERSISTENT, DOH_URL_KEY)
+		.unwrap_or_else(|| DOH_URL.into())
+}
+
+fn fetch_from_url(url: &Vec<u8>, accept: &str) -> Option<Vec<u8>> {
 	log::debug!(target: "OWNERS", "fetch_from_url url: {:?}", url);
 
 	// Convert bytes to str
@@ -205,7 +557,7 @@ fn fetch_from_url(url: &Vec<u8>) -> Option<Vec<u8>> {
 
 	// Sending the request
 	let pending = request
-	    .add_header("Accept", ACCEPT_HEADER)
+	    .add_header("Accept", accept)
 		.add_header("User-Agent", USER_AGENT)
 		.deadline(timeout)
 		.send() ;
@@ -380,31 +732,73 @@ impl OffchainCache {
 
 decl_storage! {
 	trait Store for Module<T: Config> as Owners {
+		// Version of the storage layout, used by migrations
+		StorageVersion: u8 = 0 ;
+
 		// Total number of URLs registered
 		UrlCount: u128 = 0 ;
 
 		// Price for one URL check
-    	RequestPrice: u128 = 5_000_000_000_000;
+    	RequestPrice get(fn request_price) config(): u128 = 5_000_000_000_000;
 
     	// Number of blocks after the request
     	// during which commits are allowed
-    	NumBlocksToCommit: u8 = 5 ;
+    	NumBlocksToCommit get(fn num_blocks_to_commit) config(): u8 = 5 ;
 
     	// Number of blocks after the end of commits
     	// during which reveals are allowed
-    	NumBlocksToReveal: u8 = 5 ;
+    	NumBlocksToReveal get(fn num_blocks_to_reveal) config(): u8 = 5 ;
 
 		// Number of blocks after the end of reveals
     	// during which request data is persisted
     	// After this time, requests, commits and reveals
     	// are deleted
-    	NumBlocksToDelete: u8 = 100 ;
+    	NumBlocksToDelete get(fn num_blocks_to_delete) config(): u8 = 100 ;
 
 		// Prct of identical reveals to validate a round of voting
-    	PrctNeededForAgreement: u8 = 66 ;
+    	PrctNeededForAgreement get(fn prct_needed_for_agreement) config(): u8 = 66 ;
 
 		// Minimum number of verifiers required to approve ownership
-		MajorityMin: u32 = 1 ;
+		MajorityMin get(fn majority_min) config(): u32 = 1 ;
+
+		// Number of blocks during which a verified ownership is valid
+		// 0 means ownership never expires
+		OwnershipTtl get(fn ownership_ttl) config(): u32 = 0 ;
+
+		// Prct of RequestPrice paid by owners to renew their ownership
+		RenewPricePrct get(fn renew_price_prct) config(): u8 = 50 ;
+
+		// Amount reserved from challengers until their challenge is resolved
+		ChallengeBond get(fn challenge_bond) config(): u128 = 10_000_000_000_000 ;
+
+		// Prct of the request fee paid to verifiers who voted with the majority
+		VerifiersSharePrct get(fn verifiers_share_prct) config(): u8 = 50 ;
+
+		// Number of times a request is reopened when it doesn't reach NumChecksRequired reveals
+		MaxRetries get(fn max_retries) config(): u8 = 1 ;
+
+		// Prct of the request fee refunded when no verifier revealed a vote
+		NoRevealRefundPrct get(fn no_reveal_refund_prct) config(): u8 = 100 ;
+
+		// Prct of the request fee refunded when the verification is inconclusive
+		InconclusiveRefundPrct get(fn inconclusive_refund_prct) config(): u8 = 50 ;
+
+		// Amount reserved by verifiers who register themselves
+		VerifierBond get(fn verifier_bond) config(): u128 = 100_000_000_000_000 ;
+
+		// Number of blocks after unregistering before the bond can be withdrawn
+		// Longer than the commit and reveal windows together
+		UnbondingPeriod get(fn unbonding_period) config(): u32 = 14_400 ;
+
+		// Prct of the bond slashed for each offence
+		SlashPrct get(fn slash_prct) config(): u8 = 10 ;
+
+		// Prct of minority votes above which a verifier is slashed
+		MaxMinorityPrct get(fn max_minority_prct) config(): u8 = 34 ;
+
+		// Prct of commits without reveal above which a verifier is disabled
+		// 0 turns off auto-disabling
+		MaxMissedRevealsPrct get(fn max_missed_reveals_prct) config(): u8 = 0 ;
 
     	// Registered verifiers
     	// 0. Block at which they were registered
@@ -418,13 +812,44 @@ decl_storage! {
 		// 8. votes_correct
     	Verifiers: map hasher(identity) T::AccountId => (T::BlockNumber, bool, u32, u32, u32, u32, u32, u32, u32) ;
 
+    	// Bonds reserved by verifiers who registered themselves
+    	VerifierBonds: map hasher(identity) T::AccountId => BalanceOf<T> ;
+
+    	// Block at which unregistered verifiers can withdraw their bond
+    	Unbonding: map hasher(identity) T::AccountId => T::BlockNumber ;
+
+    	// Verifiers disabled by sudo or by a penalty
+    	// They can't register again until sudo enables them
+    	SuspendedVerifiers: map hasher(identity) T::AccountId => () ;
+
+    	// Number of commits that were never revealed by each verifier
+    	MissedReveals get(fn missed_reveals): map hasher(identity) T::AccountId => u32 ;
+
     	// List of requests received by block
     	History: map hasher(identity) T::BlockNumber => Vec<Vec<u8>> ;
 
     	// Request data:
     	// - Block number
     	// - Account
-    	Requests: map hasher(blake2_128_concat) Vec<u8> => (T::BlockNumber, T::AccountId) ;
+    	// - NumBlocksToCommit when the request was created
+    	// - NumBlocksToReveal when the request was created
+    	// - NumBlocksToDelete when the request was created
+    	Requests: map hasher(blake2_128_concat) Vec<u8> => (T::BlockNumber, T::AccountId, u8, u8, u8) ;
+
+    	// Fee paid to the pot for each request
+    	RequestFees: map hasher(blake2_128_concat) Vec<u8> => BalanceOf<T> ;
+
+    	// Account that paid the fee of each request, refunded if nothing is decided
+    	RequestPayers: map hasher(blake2_128_concat) Vec<u8> => T::AccountId ;
+
+    	// Proof method verifiers should check for each request
+    	RequestProofs: map hasher(blake2_128_concat) Vec<u8> => ProofMethod ;
+
+    	// List of requests to aggregate by block
+    	AggregationQueue: map hasher(identity) T::BlockNumber => Vec<Vec<u8>> ;
+
+    	// List of requests to delete by block
+    	CleanUpQueue: map hasher(identity) T::BlockNumber => Vec<Vec<u8>> ;
 
     	// Commit data
     	// Should be the keccak_256 of the concatenation
@@ -445,10 +870,39 @@ decl_storage! {
     	// - Num votes majority
     	// - First characters of the webpage
     	// - Mark found on the page
-    	Results: map hasher(blake2_128_concat) Vec<u8> => (T::BlockNumber, u32, u32, u32, Vec<u8>, Vec<u8>, bool) ;
+    	// - Ownership approved
+    	// - State (RESULT_STATE_DECIDED or RESULT_STATE_INCONCLUSIVE)
+    	Results: map hasher(blake2_128_concat) Vec<u8> => (T::BlockNumber, u32, u32, u32, Vec<u8>, Vec<u8>, bool, u8) ;
+
+    	// Number of times a request was reopened for lack of quorum
+    	RequestRetries: map hasher(blake2_128_concat) Vec<u8> => u8 ;
 
     	// Final URL-Account map representing ownership
+    	// URLs ending with /* are prefix records covering every URL under them
     	Owners: map hasher(blake2_128_concat) Vec<u8> => T::AccountId ;
+
+    	// Reverse index of Owners: URLs owned by each account
+    	OwnedUrls: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Vec<u8> => () ;
+
+    	// Block at which the ownership of a URL expires
+    	// (no entry if the ownership doesn't expire)
+    	OwnersExpireAt: map hasher(blake2_128_concat) Vec<u8> => T::BlockNumber ;
+
+    	// Proof method used to verify the current owner of a URL
+    	OwnershipProofs: map hasher(blake2_128_concat) Vec<u8> => ProofMethod ;
+
+    	// List of ownerships to expire by block
+    	ExpiryQueue: map hasher(identity) T::BlockNumber => Vec<Vec<u8>> ;
+
+    	// Pending challenges by URL
+    	// - Challenger
+    	// - Bond reserved from the challenger
+    	Challenges: map hasher(blake2_128_concat) Vec<u8> => (T::AccountId, BalanceOf<T>) ;
+	}
+	add_extra_genesis {
+		config(verifiers): Vec<T::AccountId>;
+		config(owners): Vec<(Vec<u8>, T::AccountId)>;
+		build(|config| Module::<T>::initialize_genesis(config))
 	}
 }
 
@@ -465,7 +919,8 @@ decl_event!(
 	/// Events generated by the module.
 	pub enum Event<T>
 	where
-		AccountId = <T as frame_system::Config>::AccountId
+		AccountId = <T as frame_system::Config>::AccountId,
+		Balance = BalanceOf<T>
 	{
 		VerifierAdded(AccountId),
     	VerifierEnabled(AccountId),
@@ -474,6 +929,25 @@ decl_event!(
         UrlCheckCommitted(AccountId, Vec<u8>),
         UrlCheckRevealed(AccountId, Vec<u8>),
         UrlOwnerRegistered(AccountId, Vec<u8>, u32),
+        ParametersUpdated(OwnersParameters, OwnersParameters),
+        OwnershipTransferred(AccountId, AccountId, Vec<u8>),
+        OwnershipRenounced(AccountId, Vec<u8>),
+        OwnershipRenewalRequested(AccountId, Vec<u8>),
+        OwnershipExpired(AccountId, Vec<u8>),
+        OwnershipChallenged(AccountId, AccountId, Vec<u8>),
+        VerificationRetried(Vec<u8>, u8),
+        VerificationInconclusive(Vec<u8>),
+        RequestRefunded(AccountId, Vec<u8>, Balance),
+        ChallengeAccepted(AccountId, AccountId, Vec<u8>),
+        ChallengeRejected(AccountId, AccountId, Vec<u8>),
+        ChallengeInconclusive(AccountId, Vec<u8>),
+        VerifierPaid(AccountId, Vec<u8>, Balance),
+        VerifierBonded(AccountId, Balance),
+        VerifierUnbonding(AccountId, u32),
+        VerifierBondWithdrawn(AccountId, Balance),
+        VerifierSlashed(AccountId, Vec<u8>, Balance),
+        RevealMissed(AccountId, Vec<u8>),
+        StakingParametersUpdated(StakingParameters, StakingParameters),
 	}
 );
 
@@ -528,7 +1002,70 @@ decl_error! {
         InvalidSalt,
 
         // 14
-        CantRevealWithoutCommit
+        CantRevealWithoutCommit,
+
+        // 15
+        InvalidRequestPrice,
+
+        // 16
+        InvalidNumBlocks,
+
+        // 17
+        InvalidPercentage,
+
+        // 18
+        InvalidMajorityMin,
+
+        // 19
+        NotUrlOwner,
+
+        // 20
+        NotEnoughBalanceToRenewOwnership,
+
+        // 21
+        UrlAlreadyOwned,
+
+        // 22
+        UrlNotOwned,
+
+        // 23
+        CantChallengeOwnUrl,
+
+        // 24
+        NotEnoughBalanceToChallenge,
+
+        // 25
+        OwnershipChallenged,
+
+        // 26
+        InvalidChallengeBond,
+
+        // 27
+        NotEnoughBalanceToBond,
+
+        // 28
+        VerifierNotBonded,
+
+        // 29
+        VerifierAlreadyUnbonding,
+
+        // 30
+        UnbondingNotFinished,
+
+        // 31
+        InvalidVerifierBond,
+
+        // 32
+        InvalidUrlPrefix,
+
+        // 33
+        PrefixNeedsDomainProof,
+
+        // 34
+        InvalidUnbondingPeriod,
+
+        // 35
+        VerifierSuspended
 	}
 }
 
@@ -547,12 +1084,18 @@ impl<T:Config> OwnershipRegistry<T> for Module<T> {
         PALLET_ID.into_account()
     }
 
+	// Exact URL owner, or else the owner of the most specific prefix covering the URL
 	fn get_owner(url: &Vec<u8>) -> T::AccountId {
-		if Owners::<T>::contains_key(url) {
-			Owners::<T>::get(url)
-		} else {
-			Self::get_pot_id()
+		let url = canonicalize_url(url) ;
+		if Owners::<T>::contains_key(&url) {
+			return Owners::<T>::get(&url) ;
 		}
+		for prefix in url_prefixes(&url) {
+			if Owners::<T>::contains_key(&prefix) {
+				return Owners::<T>::get(&prefix) ;
+			}
+		}
+		Self::get_pot_id()
 	}
 
 }
@@ -578,100 +1121,502 @@ impl<T: Config> Module<T> {
 		}
 	}
 
+	// Unbonding verifiers can no longer commit but still reveal the votes they committed
+	fn can_verifier_reveal(who: &T::AccountId) -> bool {
+		Self::is_verifier_enabled(who) || Unbonding::<T>::contains_key(who)
+	}
+
+	// Unbonding must outlast the commit and reveal windows so pending votes can still be slashed
+	fn is_unbonding_period_valid(unbonding_period: u32, num_blocks_to_commit: u8, num_blocks_to_reveal: u8) -> bool {
+		let windows: u32 = num_blocks_to_commit as u32 + num_blocks_to_reveal as u32 ;
+		unbonding_period>windows
+	}
+
+	fn is_url_owner(url: &Vec<u8>, who: &T::AccountId) -> bool {
+		Owners::<T>::contains_key(url) && Owners::<T>::get(url)==*who
+	}
+
 	fn is_url_being_verified(url: &Vec<u8>) -> bool {
 		Requests::<T>::contains_key(url)
 	}
 
+	#[cfg(feature = "std")]
+	fn initialize_genesis(config: &GenesisConfig<T>) {
+		// Check parameters
+		assert!(config.request_price>0, "RequestPrice must be positive") ;
+		assert!(config.num_blocks_to_commit>=1, "NumBlocksToCommit must be at least 1") ;
+		assert!(config.num_blocks_to_reveal>=1, "NumBlocksToReveal must be at least 1") ;
+		assert!(config.num_blocks_to_delete>=1, "NumBlocksToDelete must be at least 1") ;
+		assert!(config.prct_needed_for_agreement<100, "PrctNeededForAgreement must be less than 100") ;
+		assert!(config.majority_min>=1, "MajorityMin must be at least 1") ;
+		assert!(config.renew_price_prct<=100, "RenewPricePrct must be 100 or less") ;
+		assert!(config.challenge_bond>0, "ChallengeBond must be positive") ;
+		assert!(config.verifiers_share_prct<=100, "VerifiersSharePrct must be 100 or less") ;
+		assert!(config.no_reveal_refund_prct<=100, "NoRevealRefundPrct must be 100 or less") ;
+		assert!(config.inconclusive_refund_prct<=100, "InconclusiveRefundPrct must be 100 or less") ;
+		assert!(config.verifier_bond>0, "VerifierBond must be positive") ;
+		assert!(Self::is_unbonding_period_valid(config.unbonding_period, config.num_blocks_to_commit, config.num_blocks_to_reveal),
+			"UnbondingPeriod must be longer than the commit and reveal windows") ;
+		assert!(config.slash_prct<=100, "SlashPrct must be 100 or less") ;
+		assert!(config.max_minority_prct<=100, "MaxMinorityPrct must be 100 or less") ;
+		assert!(config.max_missed_reveals_prct<=100, "MaxMissedRevealsPrct must be 100 or less") ;
+
+		// New chains start with the latest storage layout
+		StorageVersion::set(STORAGE_VERSION) ;
+
+		// Initial verifiers
+		let zero: u8 = 0 ;
+		let block: T::BlockNumber = zero.saturated_into() ;
+		for account in &config.verifiers {
+			let verifier = (block, true, 0, 0, 0, 0, 0, 0, 0) ;
+			Verifiers::<T>::insert(account, verifier) ;
+		}
+
+		// Pre-registered URL owners
+		for (url, owner) in &config.owners {
+			assert!(url.len()<T::MaxLengthURL::get().into(), "URL is too long") ;
+			Self::register_owner(&canonicalize_url(url), owner, block) ;
+		}
+	}
+
+	// Owner of a URL, used by the runtime API
+	pub fn get_url_owner(url: &Vec<u8>) -> Option<T::AccountId> {
+		let owner = <Self as OwnershipRegistry<T>>::get_owner(url) ;
+		if owner==Self::get_pot_id() {
+			None
+		} else {
+			Some(owner)
+		}
+	}
+
+	// State of a pending request, used by the runtime API
+	// - BlockNumber: Block at which the request was received
+	// - AccountId: Requester
+	// - u8: Phase (REQUEST_PHASE_COMMIT, REQUEST_PHASE_REVEAL or REQUEST_PHASE_AGGREGATED)
+	// - BlockNumber: Blocks remaining until the end of the phase
+	//                (until the request is deleted once aggregated)
+	pub fn get_request_state(url: &Vec<u8>) -> Option<(T::BlockNumber, T::AccountId, u8, T::BlockNumber)> {
+		let url = &canonicalize_url(url) ;
+		if !Requests::<T>::contains_key(url) {
+			return None ;
+		}
+		let request = Requests::<T>::get(url) ;
+		let current_block = Self::current_block_number() ;
+		let commit_deadline = Self::commit_deadline(&request) ;
+		let reveal_deadline = Self::reveal_deadline(&request) ;
+		let clean_up_block = Self::clean_up_block(&request) ;
+		let (phase, end_block) = if current_block<=commit_deadline {
+			(REQUEST_PHASE_COMMIT, commit_deadline)
+		} else if current_block<=reveal_deadline {
+			(REQUEST_PHASE_REVEAL, reveal_deadline)
+		} else {
+			(REQUEST_PHASE_AGGREGATED, clean_up_block)
+		} ;
+		let remaining = if end_block>current_block {
+			end_block - current_block
+		} else {
+			u8_to_block::<T>(0)
+		} ;
+		Some((request.0, request.1, phase, remaining))
+	}
+
+	// Verification result of a URL, used by the runtime API
+	pub fn get_result(url: &Vec<u8>) -> Option<(T::BlockNumber, u32, u32, u32, Vec<u8>, Vec<u8>, bool, u8)> {
+		let url = &canonicalize_url(url) ;
+		if Results::<T>::contains_key(url) {
+			Some(Results::<T>::get(url))
+		} else {
+			None
+		}
+	}
+
+	// URLs owned by an account, used by the runtime API
+	pub fn get_owned_urls(account: &T::AccountId) -> Vec<Vec<u8>> {
+		OwnedUrls::<T>::iter_prefix(account)
+			.map(|(url, _)| url)
+			.collect()
+	}
+
+	// Register the owner of a URL, keeping the reverse index in sync
+	fn set_owner(url: &Vec<u8>, owner: &T::AccountId) {
+		if Owners::<T>::contains_key(url) {
+			let previous = Owners::<T>::get(url) ;
+			OwnedUrls::<T>::remove(&previous, url) ;
+		}
+		Owners::<T>::insert(url, owner) ;
+		OwnedUrls::<T>::insert(owner, url, ()) ;
+	}
+
+	// Remove the owner of a URL, keeping the reverse index in sync
+	fn remove_owner(url: &Vec<u8>) {
+		if Owners::<T>::contains_key(url) {
+			let previous = Owners::<T>::take(url) ;
+			OwnedUrls::<T>::remove(&previous, url) ;
+		}
+		OwnersExpireAt::<T>::remove(url) ;
+		OwnershipProofs::remove(url) ;
+	}
+
+	// Register a verified owner and start a new ownership period
+	fn register_owner(url: &Vec<u8>, owner: &T::AccountId, current_block: T::BlockNumber) {
+		Self::set_owner(url, owner) ;
+		let ttl = OwnershipTtl::get() ;
+		if ttl>0 {
+			let expire_at = current_block + u32_to_block::<T>(ttl) ;
+			OwnersExpireAt::<T>::insert(url, expire_at) ;
+			ExpiryQueue::<T>::mutate(expire_at, |urls| Self::push_to_queue(urls, url)) ;
+		} else {
+			OwnersExpireAt::<T>::remove(url) ;
+		}
+	}
+
+	fn expire_ownerships(current_block: T::BlockNumber) -> Weight {
+		log::debug!(target: "OWNERS", "expire_ownerships current_block: {:?}", current_block);
+		let urls = ExpiryQueue::<T>::take(current_block) ;
+		// Each URL reads its expiry and owner, and may remove the owner and its index entries
+		let count: Weight = urls.len().saturated_into() ;
+		let weight = T::DbWeight::get().reads_writes(1+count.saturating_mul(2), 1+count.saturating_mul(4)) ;
+		for url in urls {
+			// Skip ownerships that were renewed, renounced or registered again since
+			let expired = OwnersExpireAt::<T>::contains_key(&url) && OwnersExpireAt::<T>::get(&url)==current_block ;
+			if expired && Owners::<T>::contains_key(&url) {
+				log::debug!(target: "OWNERS", "expire_ownerships url: {:?}", sp_std::str::from_utf8(&url));
+				let owner = Owners::<T>::get(&url) ;
+				Self::remove_owner(&url) ;
+				Self::deposit_event(RawEvent::OwnershipExpired(owner, url));
+			}
+		}
+		weight
+	}
+
+	fn get_staking_parameters() -> StakingParameters {
+		(
+			VerifierBond::get(),
+			UnbondingPeriod::get(),
+			SlashPrct::get(),
+			MaxMinorityPrct::get(),
+			MaxMissedRevealsPrct::get()
+		)
+	}
+
+	// Disable a verifier until sudo enables it again
+	fn suspend_verifier(account: &T::AccountId) {
+		let mut verifier = Verifiers::<T>::take(account) ;
+		verifier.1 = false ;
+		Verifiers::<T>::insert(account, &verifier) ;
+		SuspendedVerifiers::<T>::insert(account, ()) ;
+		Self::deposit_event(RawEvent::VerifierDisabled(account.clone()));
+	}
+
+	// Send SlashPrct of a verifier's bond to the pot
+	// Verifiers are disabled once their bond is gone
+	// Verifiers added by sudo have no bond and are never slashed, sudo disables them instead
+	fn slash_verifier(account: &T::AccountId, url: &Vec<u8>) {
+		if !VerifierBonds::<T>::contains_key(account) {
+			return ;
+		}
+		let bond = VerifierBonds::<T>::get(account) ;
+		let bond_u128: u128 = bond.saturated_into() ;
+		let prct: u128 = SlashPrct::get().into() ;
+		let amount = u128_to_balance::<T>(bond_u128.saturating_mul(prct) / 100) ;
+		let pot = Self::get_pot_id() ;
+		let slashed = match T::Currency::repatriate_reserved(account, &pot, amount, BalanceStatus::Free) {
+			Ok(not_slashed) => amount.saturating_sub(not_slashed),
+			Err(_) => Zero::zero()
+		} ;
+		let update = bond.saturating_sub(slashed) ;
+		VerifierBonds::<T>::insert(account, update) ;
+		log::debug!(target: "OWNERS", "slash_verifier {:?} slashed: {:?}", account, slashed);
+		Self::deposit_event(RawEvent::VerifierSlashed(account.clone(), url.clone(), slashed));
+		if update.is_zero() && Self::is_verifier_enabled(account) {
+			Self::suspend_verifier(account) ;
+		}
+	}
+
+	// Count, report and slash verifiers who committed without revealing
+	// Verifiers above MaxMissedRevealsPrct are disabled
+	// Returns the number of commits of the request
+	fn process_missed_reveals(url: &Vec<u8>) -> u32 {
+		let max_prct: u32 = MaxMissedRevealsPrct::get().into() ;
+		let mut count: u32 = 0 ;
+		for (account, _) in Commits::<T>::iter_prefix(url) {
+			count += 1 ;
+			if !Reveals::<T>::contains_key(url, &account) {
+				log::debug!(target: "OWNERS", "process_missed_reveals missing reveal: {:?}", &account);
+				let missed = MissedReveals::<T>::get(&account) + 1 ;
+				MissedReveals::<T>::insert(&account, missed) ;
+				Self::deposit_event(RawEvent::RevealMissed(account.clone(), url.clone()));
+				Self::slash_verifier(&account, url) ;
+				let commits = Verifiers::<T>::get(&account).2 ;
+				if max_prct>0
+					&& commits>=MIN_COMMITS_FOR_MISS_RATE
+					&& missed*100/commits>max_prct
+					&& Self::is_verifier_enabled(&account) {
+					Self::suspend_verifier(&account) ;
+				}
+			}
+		}
+		count
+	}
+
+	fn pay_verifiers(url: &Vec<u8>, verifiers: &Vec<T::AccountId>) {
+		let num_verifiers: u128 = verifiers.len().saturated_into() ;
+		if num_verifiers==0 {
+			return ;
+		}
+		let fee: u128 = RequestFees::<T>::get(url).saturated_into() ;
+		let share: u128 = VerifiersSharePrct::get().into() ;
+		let amount = u128_to_balance::<T>(fee.saturating_mul(share) / 100 / num_verifiers) ;
+		if amount==u128_to_balance::<T>(0) {
+			return ;
+		}
+		// The pot may be emptied, the next fee creates it again
+		let pot = Self::get_pot_id() ;
+		for verifier in verifiers {
+			let transfer = T::Currency::transfer(&pot, verifier, amount, ExistenceRequirement::AllowDeath) ;
+			if transfer.is_err() {
+				log::error!(target: "OWNERS", "pay_verifiers transfer failed: {:?}", verifier);
+			} else {
+				log::debug!(target: "OWNERS", "pay_verifiers paid {:?} to {:?}", amount, verifier);
+				Self::deposit_event(RawEvent::VerifierPaid(verifier.clone(), url.clone(), amount));
+			}
+		}
+	}
+
+	// Return a prct of the request fee from the pot to whoever paid it
+	fn refund_request(url: &Vec<u8>, prct: u8) {
+		if prct==0 || !RequestPayers::<T>::contains_key(url) {
+			return ;
+		}
+		let payer = RequestPayers::<T>::get(url) ;
+		let fee: u128 = RequestFees::<T>::get(url).saturated_into() ;
+		let prct: u128 = prct.into() ;
+		let amount = u128_to_balance::<T>(fee.saturating_mul(prct) / 100) ;
+		if amount==u128_to_balance::<T>(0) {
+			return ;
+		}
+		// The pot may be emptied, the next fee creates it again
+		let pot = Self::get_pot_id() ;
+		let transfer = T::Currency::transfer(&pot, &payer, amount, ExistenceRequirement::AllowDeath) ;
+		if transfer.is_err() {
+			log::error!(target: "OWNERS", "refund_request transfer failed: {:?}", payer);
+		} else {
+			log::debug!(target: "OWNERS", "refund_request refunded {:?} to {:?}", amount, payer);
+			Self::deposit_event(RawEvent::RequestRefunded(payer, url.clone(), amount));
+		}
+	}
+
+	// Settle a challenge once votes are aggregated
+	// mark_found is None when verifiers did not reach an agreement
+	fn resolve_challenge(url: &Vec<u8>, mark_found: Option<bool>) {
+		if !Challenges::<T>::contains_key(url) {
+			return ;
+		}
+		let (challenger, bond) = Challenges::<T>::take(url) ;
+		let (_, challenged, _, _, _) = Requests::<T>::get(url) ;
+		let still_owner = Self::is_url_owner(url, &challenged) ;
+		log::debug!(target: "OWNERS", "resolve_challenge mark_found: {:?}", mark_found);
+		match mark_found {
+			Some(true) => {
+				// The owner's mark is on the page, the challenger loses the bond
+				let pot = Self::get_pot_id() ;
+				let _ = T::Currency::repatriate_reserved(&challenger, &pot, bond, BalanceStatus::Free) ;
+				Self::deposit_event(RawEvent::ChallengeRejected(challenger, challenged, url.clone()));
+			},
+			Some(false) if still_owner => {
+				// The owner's mark is gone, the ownership is cleared
+				T::Currency::unreserve(&challenger, bond) ;
+				Self::remove_owner(url) ;
+				Self::deposit_event(RawEvent::ChallengeAccepted(challenger, challenged, url.clone()));
+			},
+			_ => {
+				// No agreement or the owner changed in the meantime
+				T::Currency::unreserve(&challenger, bond) ;
+				Self::deposit_event(RawEvent::ChallengeInconclusive(challenger, url.clone()));
+			}
+		}
+	}
+
+	// Common checks and payment for verification requests
+	// The sender pays for the request, verifiers look for the requester's mark
+	fn submit_request(sender: &T::AccountId, url: &Vec<u8>, price: u128, requester: &T::AccountId,
+					  proof_method: ProofMethod, not_enough_balance: Error<T>) -> DispatchResult {
+		// Check URL length
+		ensure!(url.len()<T::MaxLengthURL::get().into(), Error::<T>::UrlTooLong) ;
+
+		// Check that the signer has enough funds to be sent to the pot and stay alive
+		let price = u128_to_balance::<T>(price) ;
+		let free = T::Currency::free_balance(sender) ;
+		ensure!(free>=price.saturating_add(T::Currency::minimum_balance()), not_enough_balance) ;
+
+		// Check that that this URL is not already in the queue
+		ensure!(!Self::is_url_being_verified(url), Error::<T>::UrlCheckAlreadyInQueue) ;
+
+		// Send check price to pot
+		Self::send_to_pot(sender, price)?;
+		RequestFees::<T>::insert(url, price) ;
+		RequestPayers::<T>::insert(url, sender) ;
+		RequestProofs::insert(url, proof_method) ;
+
+		// Insert the URL in the check request queue at current block
+		let current_block = <frame_system::Pallet<T>>::block_number();
+		Self::create_request(&current_block, url, requester) ;
+		log::debug!(target: "OWNERS", "submit_request inserted at block: {:?}", &current_block);
+		Ok(())
+	}
+
+	fn get_parameters() -> OwnersParameters {
+		(
+			RequestPrice::get(),
+			NumBlocksToCommit::get(),
+			NumBlocksToReveal::get(),
+			NumBlocksToDelete::get(),
+			PrctNeededForAgreement::get(),
+			MajorityMin::get(),
+			OwnershipTtl::get(),
+			RenewPricePrct::get(),
+			ChallengeBond::get(),
+			VerifiersSharePrct::get(),
+			MaxRetries::get(),
+			NoRevealRefundPrct::get(),
+			InconclusiveRefundPrct::get()
+		)
+	}
+
+	// Last block to accept commits for a request
+	fn commit_deadline(request: &RequestOf<T>) -> T::BlockNumber {
+		request.0 + u8_to_block::<T>(request.2)
+	}
+
+	// Last block to accept reveals for a request
+	fn reveal_deadline(request: &RequestOf<T>) -> T::BlockNumber {
+		Self::commit_deadline(request) + u8_to_block::<T>(request.3)
+	}
+
+	// Block at which the votes of a request are aggregated
+	fn aggregation_block(request: &RequestOf<T>) -> T::BlockNumber {
+		Self::reveal_deadline(request) + u8_to_block::<T>(1)
+	}
+
+	// Block at which a request and its votes are deleted
+	fn clean_up_block(request: &RequestOf<T>) -> T::BlockNumber {
+		Self::aggregation_block(request) + u8_to_block::<T>(request.4)
+	}
+
+	fn push_to_queue(queue: &mut Vec<Vec<u8>>, url: &Vec<u8>) {
+		if !queue.contains(url) {
+			queue.push(url.clone()) ;
+		}
+	}
+
+	fn schedule_request(url: &Vec<u8>, request: &RequestOf<T>, current_block: T::BlockNumber) {
+		let aggregate_at = Self::aggregation_block(request) ;
+		if aggregate_at>=current_block {
+			AggregationQueue::<T>::mutate(aggregate_at, |urls| Self::push_to_queue(urls, url)) ;
+		}
+		let mut clean_up_at = Self::clean_up_block(request) ;
+		if clean_up_at<current_block {
+			clean_up_at = current_block ;
+		}
+		CleanUpQueue::<T>::mutate(clean_up_at, |urls| Self::push_to_queue(urls, url)) ;
+	}
+
+	// Reopen a request that did not reach quorum, keeping the fee already paid
+	// Returns false once MaxRetries is reached
+	fn retry_request(current_block: T::BlockNumber, url: &Vec<u8>) -> bool {
+		let retries = RequestRetries::get(url) ;
+		if retries>=MaxRetries::get() {
+			return false ;
+		}
+		let (block, requester, _, _, _) = Requests::<T>::get(url) ;
+		Self::remove_from_history(block, url) ;
+		Commits::<T>::remove_prefix(url, None) ;
+		Reveals::<T>::remove_prefix(url, None) ;
+		Self::create_request(&current_block, url, &requester) ;
+		RequestRetries::insert(url, retries+1) ;
+		log::debug!(target: "OWNERS", "retry_request retries: {:?}", retries+1);
+		Self::deposit_event(RawEvent::VerificationRetried(url.clone(), retries+1));
+		true
+	}
+
 	fn create_request(block: &T::BlockNumber, url: &Vec<u8>, sender: &T::AccountId) {
 		let mut urls = History::<T>::take(block);
 		urls.push(url.clone());
 		History::<T>::insert(block, urls);
-		Requests::<T>::insert(url, (block,sender)) ;
+		let request: RequestOf<T> = (
+			*block,
+			sender.clone(),
+			NumBlocksToCommit::get(),
+			NumBlocksToReveal::get(),
+			NumBlocksToDelete::get()
+		) ;
+		Self::schedule_request(url, &request, *block) ;
+		Requests::<T>::insert(url, request) ;
 	}
 
-	fn send_to_pot(sender: &T::AccountId, amount: BalanceOf<T>) {
+	fn send_to_pot(sender: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
 		log::debug!(target: "OWNERS", "Sending likes to pot: {:?}", amount);
 		T::Currency::transfer(sender,
 							  &Self::get_pot_id(),
 							  amount,
-							  ExistenceRequirement::KeepAlive).expect("balance was already checked");
+							  ExistenceRequirement::KeepAlive)
 	}
 
-	fn get_local_verifier() -> Option<[u8; 32]> {
+	// First local key whose account passes the check
+	fn get_local_verifier(check: fn(&T::AccountId) -> bool) -> Option<[u8; 32]> {
 		let keys: Vec<PublicOf<T>> = PublicOf::<T>::all() ;
 		for x in keys {
 			let bytes: [u8; 32] = x.as_ref().try_into().expect("cant fail") ;
 			let account: T::AccountId = T::AccountId::decode(&mut &bytes[..]).expect("never fails") ;
 			log::debug!(target: "OWNERS", "offchain_worker account: {:?}", &account);
-			if Self::is_verifier_enabled(&account) {
+			if check(&account) {
 				return Some(bytes) ;
 			}
 		}
 		return None ;
 	}
 
-	fn check_url_offchain(url: &Vec<u8>, requester: &T::AccountId, requested_at: T::BlockNumber) {
-		log::debug!(target: "OWNERS", "check_url_offchain: {:?}", url);
+	fn check_url_offchain(url: &Vec<u8>, requester: &T::AccountId, commit_deadline: T::BlockNumber, proof_method: ProofMethod) {
+		log::debug!(target: "OWNERS", "check_url_offchain: {:?} {:?}", url, proof_method);
+
+		// Find the document holding the proof
+		let location = proof_location(url, proof_method, &doh_resolver()) ;
+		if location.is_none() {
+			log::debug!(target: "OWNERS", "check_url_offchain could not find the host of the url");
+			return ;
+		}
+		let (location, accept) = location.unwrap() ;
 
 		// Fetch data from url
-		let bytes = fetch_from_url(url) ;
+		let bytes = fetch_from_url(&location, accept) ;
 		if bytes.is_none() {
 			log::debug!(target: "OWNERS", "check_url_offchain could not fetch data from url");
 			return ;
 		}
 		let bytes = bytes.unwrap() ;
 
-		// Convert to str
-		let data = sp_std::str::from_utf8(&bytes) ;
-		if data.is_err() {
-			log::debug!(target: "OWNERS", "check_url_offchain could not convert bytes to str");
-			return ;
-		}
-		let data = data.unwrap() ;
-
-		// Intro part
-		let intro = &data[..INTRO_LENGTH] ;
-		log::debug!(target: "OWNERS", "check_url_offchain intro: {:?}", intro);
-		let intro: Vec<u8> = intro.into() ;
-
-		// Mark part
-		let mark_idx = data.find(MARK_PREFIX) ;
-		if mark_idx.is_none() {
+		// Intro and mark parts
+		let (intro, mark) = extract_proof(proof_method, &bytes) ;
+		log::debug!(target: "OWNERS", "check_url_offchain intro: {:?}", sp_std::str::from_utf8(&intro));
+		if mark.is_none() {
 			log::debug!(target: "OWNERS", "check_url_offchain mark not found, voting NO");
-			Self::send_commit_offchain(url, requested_at, false, &intro, None) ;
+			Self::send_commit_offchain(url, commit_deadline, false, &intro, None) ;
 			return ;
 		}
-		let mark_idx = mark_idx.unwrap() ;
-		let mark_str = &data[mark_idx..mark_idx+MARK_LENGTH] ;
-		log::debug!(target: "OWNERS", "check_url_offchain mark_str: {:?}", mark_str);
+		let mark = mark.unwrap() ;
+		log::debug!(target: "OWNERS", "check_url_offchain mark: {:?}", sp_std::str::from_utf8(&mark));
 
 		// Check that the mark contains the address
 		log::debug!(target: "OWNERS", "check_url_offchain requester: {:?}", &requester);
-		let address: [u8; 32] = requester.encode().try_into().expect("address is always 32") ;
-		let mut address_hex: [u8; 64] = [0; 64] ;
-		let conversion = hex::encode_to_slice(address, &mut address_hex) ;
-		if conversion.is_err() {
-			log::debug!(target: "OWNERS", "check_url_offchain could not convert address to hex");
-			return ;
-		}
-		let address = sp_std::str::from_utf8(&address_hex) ;
-		if address.is_err() {
-			log::debug!(target: "OWNERS", "check_url_offchain could not convert address to str");
-			return ;
-		}
-		let address = address.unwrap() ;
-		log::debug!(target: "OWNERS", "check_url_offchain address: {:?}", &address);
-		let address_idx = mark_str.find(&address) ;
-		if address_idx.is_none() {
+		if !proof_matches_account(proof_method, &mark, requester) {
 			log::debug!(target: "OWNERS", "check_url_offchain mark address does not match, voting NO");
-			Self::send_commit_offchain(url, requested_at, false, &intro, None) ;
+			Self::send_commit_offchain(url, commit_deadline, false, &intro, None) ;
 			return ;
 		}
 
 		// Valid mark found, let's vote YES
-		let proof: Vec<u8> = mark_str.into() ;
 		log::debug!(target: "OWNERS", "check_url_offchain voting YES");
-		Self::send_commit_offchain(url, requested_at, true, &intro, Some(&proof)) ;
+		Self::send_commit_offchain(url, commit_deadline, true, &intro, Some(&mark)) ;
 	}
 
 	fn concat_data1(vote: bool, intro: &Vec<u8>, proof: Option<&Vec<u8>>) -> Vec<u8> {
@@ -708,14 +1653,14 @@ impl<T: Config> Module<T> {
 		ans
 	}
 
-	fn send_commit_offchain(url: &Vec<u8>, requested_at: T::BlockNumber, vote: bool, intro: &Vec<u8>, proof: Option<&Vec<u8>>) {
+	fn send_commit_offchain(url: &Vec<u8>, commit_deadline: T::BlockNumber, vote: bool, intro: &Vec<u8>, proof: Option<&Vec<u8>>) {
 		// Concatenate the 3 parameters
 		let concat1: Vec<u8> = Self::concat_data1(vote, intro, proof) ;
 		log::debug!(target: "OWNERS", "send_commit_offchain concat1.len(): {:?}", concat1.len());
 		log::debug!(target: "OWNERS", "send_commit_offchain concat1: {:?}", &concat1);
 
 		// Sign this part to get the salt
-		let verifier = Self::get_local_verifier() ;
+		let verifier = Self::get_local_verifier(Self::is_verifier_enabled) ;
 		if verifier.is_none() {
 			log::debug!(target: "OWNERS", "send_commit_offchain unable to find verifier");
 			return ;
@@ -745,8 +1690,7 @@ impl<T: Config> Module<T> {
 		// Check that it's still time to commit
 		let current_block = Self::current_block_number() ;
 		log::debug!(target: "OWNERS", "send_commit_offchain current_block: {:?}", current_block);
-		let param = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-		let max_block = requested_at + param ;
+		let max_block = commit_deadline ;
 		if current_block>=max_block {
 			log::debug!(target: "OWNERS", "send_commit_offchain too late to commit");
 			return ;
@@ -782,10 +1726,8 @@ impl<T: Config> Module<T> {
 		let request = Requests::<T>::get(url) ;
 		let request_block = request.0 ;
 		let current_block = Self::current_block_number() ;
-		let param1 = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-		let param2 = u8_to_block::<T>(NumBlocksToReveal::get()) ;
-		let min_block = request_block + param1 ;
-		let max_block = request_block + param1 + param2 ;
+		let min_block = Self::commit_deadline(&request) ;
+		let max_block = Self::reveal_deadline(&request) ;
 		let timing_ok = current_block>=min_block && current_block<max_block ;
 		// Note that at this point current_block is already finalized so it's ok to trigger the tx now,
 		// it can only be included in next block, thus current_block>=min_block
@@ -819,37 +1761,33 @@ impl<T: Config> Module<T> {
 		}
 	}
 
-	fn aggregate_votes(current_block: T::BlockNumber) {
+	fn aggregate_votes(current_block: T::BlockNumber) -> Weight {
 		log::debug!(target: "OWNERS", "aggregate_votes current_block: {:?}", current_block);
-		let param1 = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-		let param2 = u8_to_block::<T>(NumBlocksToReveal::get()) ;
-		let delta = param1+param2+u8_to_block::<T>(1) ;
-		if current_block>delta {
-			let block = current_block - delta ;
-			log::debug!(target: "OWNERS", "aggregate_votes block: {:?}", block);
-			let requests = History::<T>::get(block) ;
-			log::debug!(target: "OWNERS", "aggregate_votes requests.len(): {:?}", requests.len());
-			for url in requests {
-				Self::aggregate_votes_for_request(current_block, url) ;
-			}
+		let requests = AggregationQueue::<T>::take(current_block) ;
+		log::debug!(target: "OWNERS", "aggregate_votes requests.len(): {:?}", requests.len());
+		let mut weight = T::DbWeight::get().reads_writes(1, 1) ;
+		for url in requests {
+			weight = weight.saturating_add(Self::aggregate_votes_for_request(current_block, url)) ;
 		}
+		weight
+	}
+
+	// Weight of aggregating a request, depending on its number of commits
+	// - Request data, result, challenge and ownership: 10 reads and 10 writes
+	// - Each commit reads its reveal, the verifier stats, bond and missed reveals,
+	//   and may update them, pay the verifier or remove the vote for a retry
+	fn aggregation_weight(commits: u32) -> Weight {
+		let commits: Weight = commits.into() ;
+		T::DbWeight::get().reads_writes(10+commits.saturating_mul(5), 10+commits.saturating_mul(8))
 	}
 
-	fn aggregate_votes_for_request(current_block: T::BlockNumber, url: Vec<u8>) {
+	fn aggregate_votes_for_request(current_block: T::BlockNumber, url: Vec<u8>) -> Weight {
 		log::debug!(target: "OWNERS", "aggregate_votes_for_request url: {:?}", sp_std::str::from_utf8(&url));
 		let reveals = Reveals::<T>::iter_prefix(&url).collect::<Vec<(T::AccountId, (bool, Vec<u8>, Vec<u8>))>>() ;
 		let total: u32 = reveals.len().try_into().expect("should always fit in 32") ;
 		log::debug!(target: "OWNERS", "aggregate_votes_for_request total: {:?}", total);
-		if total==0 {
-			return ;
-		}
-
-		// Define majority
-		let majority = find_majority(&reveals).unwrap() ;
-		let ((vote, intro, proof), count_majority) = majority ;
-		log::debug!(target: "OWNERS", "aggregate_votes_for_request majority count_majority: {:?}", count_majority);
-		let prct: u32 = count_majority*100/total ;
-		log::debug!(target: "OWNERS", "aggregate_votes_for_request majority prct: {:?}", prct);
+		let commits = Self::process_missed_reveals(&url) ;
+		let weight = Self::aggregation_weight(commits) ;
 
 		// Count number of yes votes
 		let mut count_yes: u32 = 0 ;
@@ -858,71 +1796,255 @@ impl<T: Config> Module<T> {
 		}
 		log::debug!(target: "OWNERS", "aggregate_votes_for_request majority count_yes: {:?}", count_yes);
 
+		// No outcome is recorded below NumChecksRequired reveals
+		// The request is reopened until MaxRetries is reached
+		let quorum: u32 = max(T::NumChecksRequired::get().into(), 1) ;
+		if total<quorum {
+			log::debug!(target: "OWNERS", "aggregate_votes_for_request quorum not reached");
+			if Self::retry_request(current_block, &url) {
+				return weight ;
+			}
+			let empty: Vec<u8> = sp_std::vec![] ;
+			let result: (T::BlockNumber, u32, u32, u32, &Vec<u8>, &Vec<u8>, bool, u8) = (
+				Self::current_block_number(),
+				total,
+				count_yes,
+				0,
+				&empty,
+				&empty,
+				false,
+				RESULT_STATE_INCONCLUSIVE
+			) ;
+			Results::<T>::insert(&url, result) ;
+			Self::deposit_event(RawEvent::VerificationInconclusive(url.clone()));
+			let refund_prct = if total==0 { NoRevealRefundPrct::get() } else { InconclusiveRefundPrct::get() } ;
+			Self::refund_request(&url, refund_prct) ;
+			Self::resolve_challenge(&url, None) ;
+			return weight ;
+		}
+
+		// Define majority using the configured vote weights
+		let majority = find_majority(&reveals, |account| T::VoteWeighting::weight(account)).unwrap() ;
+		let ((vote, intro, proof), weight_majority) = majority ;
+		let mut weight_total: u128 = 0 ;
+		let mut count_majority: u32 = 0 ;
+		for (account, (r_vote, r_intro, r_proof)) in &reveals {
+			weight_total += T::VoteWeighting::weight(account) ;
+			if (r_vote, r_intro, r_proof) == (vote, intro, proof) {
+				count_majority += 1 ;
+			}
+		}
+		log::debug!(target: "OWNERS", "aggregate_votes_for_request majority count_majority: {:?}", count_majority);
+		let prct: u32 = if weight_total==0 {
+			0
+		} else {
+			(weight_majority*100/weight_total).saturated_into()
+		} ;
+		log::debug!(target: "OWNERS", "aggregate_votes_for_request majority prct: {:?}", prct);
+
 		// Save the results
 		let bar: u32 = PrctNeededForAgreement::get().into() ;
 		let majority_min: u32 = MajorityMin::get().into() ;
-		let outcome = *vote && prct>bar && count_majority>=majority_min ;
-		let result: (T::BlockNumber, u32, u32, u32, &Vec<u8>, &Vec<u8>, bool) = (
+		let agreement = prct>bar && count_majority>=majority_min ;
+		let outcome = *vote && agreement ;
+		let state = if agreement { RESULT_STATE_DECIDED } else { RESULT_STATE_INCONCLUSIVE } ;
+		let result: (T::BlockNumber, u32, u32, u32, &Vec<u8>, &Vec<u8>, bool, u8) = (
 			Self::current_block_number(),
 			total,
 			count_yes,
 			count_majority,
 			intro,
 			proof,
-			outcome
+			outcome,
+			state
 		) ;
 		Results::<T>::insert(&url, result) ;
+		if !agreement {
+			Self::deposit_event(RawEvent::VerificationInconclusive(url.clone()));
+			Self::refund_request(&url, InconclusiveRefundPrct::get()) ;
+		}
 
 		// Update verifiers' stats if prct majority passed the bar
 		// If not, these votes won't count in the verifier stats
+		// Verifiers who voted with the majority share the verifiers' part of the fee,
+		// only when they reached an agreement since inconclusive requests are refunded
 		if prct>bar {
 			log::debug!(target: "OWNERS", "aggregate_votes_for_request votes are valid");
+			let mut correct: Vec<T::AccountId> = sp_std::vec![] ;
 			for (account, (r_vote, r_intro, r_proof)) in &reveals {
 				let mut stats = Verifiers::<T>::get(account) ;
 				stats.6 += 1 ;
 				if *r_vote {
 					stats.7 += 1 ;
 				}
-				if (r_vote, r_intro, r_proof) == (vote, intro, proof) {
+				let in_majority = (r_vote, r_intro, r_proof) == (vote, intro, proof) ;
+				if in_majority {
 					stats.8 += 1 ;
+					correct.push(account.clone()) ;
+				}
+				Verifiers::<T>::insert(account, stats) ;
+				// Slash minority votes from verifiers who are too often in the minority
+				let minority_prct: u32 = (stats.6 - stats.8)*100/stats.6 ;
+				if !in_majority && minority_prct>MaxMinorityPrct::get().into() {
+					Self::slash_verifier(account, &url) ;
 				}
 			}
+			if agreement {
+				Self::pay_verifiers(&url, &correct) ;
+			}
+		}
+
+		// Challenges don't register owners, they confirm or clear the current one
+		if Challenges::<T>::contains_key(&url) {
+			let mark_found = if agreement { Some(*vote) } else { None } ;
+			Self::resolve_challenge(&url, mark_found) ;
+			return weight ;
 		}
 
 		// Register new ownership if approved
 		// Rules of approval:
 		// - Majority voted YES
-		// - Majority represents at least PrctNeededForAgreement
+		// - Majority represents more than PrctNeededForAgreement
 		// - Majority is at least MajorityMin
 		if outcome {
 			log::debug!(target: "OWNERS", "aggregate_votes_for_request ownership approved") ;
-			let (_, owner) = Requests::<T>::get(&url) ;
-			Owners::<T>::insert(&url, &owner) ;
+			let (_, owner, _, _, _) = Requests::<T>::get(&url) ;
+			// Requests received before URLs were canonicalized are registered under the canonical URL
+			let owned_url = canonicalize_url(&url) ;
+			Self::register_owner(&owned_url, &owner, current_block) ;
+			OwnershipProofs::insert(&owned_url, RequestProofs::get(&url)) ;
 			// Emit an event that new ownership.
-            Self::deposit_event(RawEvent::UrlOwnerRegistered(owner, url, block_to_u32::<T>(current_block)));
+            Self::deposit_event(RawEvent::UrlOwnerRegistered(owner, owned_url, block_to_u32::<T>(current_block)));
 		}
 		log::debug!(target: "OWNERS", "aggregate_votes_for_request DONE");
+		weight
 	}
 
-	fn clean_up(current_block: T::BlockNumber) {
+	fn clean_up(current_block: T::BlockNumber) -> Weight {
 		log::debug!(target: "OWNERS", "clean_up current_block: {:?}", current_block);
-		let param1 = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-		let param2 = u8_to_block::<T>(NumBlocksToReveal::get()) ;
-		let param3 = u8_to_block::<T>(NumBlocksToDelete::get()) ;
-		let delta = param1+param2+param3+u8_to_block::<T>(1) ;
-		if current_block>delta {
-			let block = current_block - delta ;
-			log::debug!(target: "OWNERS", "clean_up block: {:?}", block);
-			let urls = History::<T>::take(block) ;
-			for url in urls {
-				log::debug!(target: "OWNERS", "clean_up url: {:?}", sp_std::str::from_utf8(&url));
-				Requests::<T>::remove(&url) ;
-				Commits::<T>::remove_prefix(&url, None) ;
-				Reveals::<T>::remove_prefix(&url, None) ;
-				Results::<T>::remove(&url) ;
+		let urls = CleanUpQueue::<T>::take(current_block) ;
+		let mut weight = T::DbWeight::get().reads_writes(1, 1) ;
+		for url in urls {
+			log::debug!(target: "OWNERS", "clean_up url: {:?}", sp_std::str::from_utf8(&url));
+			let request = Requests::<T>::get(&url) ;
+			weight = weight.saturating_add(T::DbWeight::get().reads(1)) ;
+			// Retried requests were rescheduled for a later clean up
+			if Self::clean_up_block(&request)>current_block {
+				continue ;
 			}
+			Self::remove_from_history(request.0, &url) ;
+			Requests::<T>::remove(&url) ;
+			RequestFees::<T>::remove(&url) ;
+			RequestPayers::<T>::remove(&url) ;
+			RequestProofs::remove(&url) ;
+			RequestRetries::remove(&url) ;
+			let commits = num_removed(Commits::<T>::remove_prefix(&url, None)) ;
+			let reveals = num_removed(Reveals::<T>::remove_prefix(&url, None)) ;
+			Results::<T>::remove(&url) ;
+			// History and the 7 request records
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 8+commits+reveals)) ;
 		}
 		log::debug!(target: "OWNERS", "clean_up DONE");
+		weight
+	}
+
+	fn remove_from_history(block: T::BlockNumber, url: &Vec<u8>) {
+		let mut urls = History::<T>::take(block) ;
+		urls.retain(|x| x!=url) ;
+		if !urls.is_empty() {
+			History::<T>::insert(block, urls) ;
+		}
+	}
+
+	// Migration to v1:
+	// Requests now record the timing parameters they were created with,
+	// and aggregation/clean up are scheduled by block
+	fn migrate_to_v1() -> Weight {
+		log::info!(target: "OWNERS", "migrate_to_v1 starting");
+		let current_block = Self::current_block_number() ;
+		let commit = NumBlocksToCommit::get() ;
+		let reveal = NumBlocksToReveal::get() ;
+		let delete = NumBlocksToDelete::get() ;
+		let mut migrated: Vec<(Vec<u8>, RequestOf<T>)> = sp_std::vec![] ;
+		Requests::<T>::translate::<(T::BlockNumber, T::AccountId), _>(|url, (block, account)| {
+			let request: RequestOf<T> = (block, account, commit, reveal, delete) ;
+			migrated.push((url, request.clone())) ;
+			Some(request)
+		});
+		for (url, request) in &migrated {
+			Self::schedule_request(url, request, current_block) ;
+		}
+		StorageVersion::set(1) ;
+		let count: Weight = migrated.len().try_into().unwrap_or(Weight::max_value()) ;
+		log::info!(target: "OWNERS", "migrate_to_v1 requests migrated: {:?}", count);
+		T::DbWeight::get().reads_writes(count+1, count.saturating_mul(3)+1)
+	}
+
+	// Migration to v2:
+	// Backfill the OwnedUrls reverse index from Owners
+	fn migrate_to_v2() -> Weight {
+		log::info!(target: "OWNERS", "migrate_to_v2 starting");
+		let mut count: Weight = 0 ;
+		for (url, owner) in Owners::<T>::iter() {
+			OwnedUrls::<T>::insert(&owner, &url, ()) ;
+			count += 1 ;
+		}
+		StorageVersion::set(2) ;
+		log::info!(target: "OWNERS", "migrate_to_v2 urls indexed: {:?}", count);
+		T::DbWeight::get().reads_writes(count+1, count+1)
+	}
+
+	// Migration to v3:
+	// Results now record whether a decision was reached
+	fn migrate_to_v3() -> Weight {
+		log::info!(target: "OWNERS", "migrate_to_v3 starting");
+		let mut count: Weight = 0 ;
+		Results::<T>::translate::<(T::BlockNumber, u32, u32, u32, Vec<u8>, Vec<u8>, bool), _>(
+			|_, (block, total, count_yes, count_majority, intro, proof, outcome)| {
+				count += 1 ;
+				Some((block, total, count_yes, count_majority, intro, proof, outcome, RESULT_STATE_DECIDED))
+			}
+		);
+		StorageVersion::set(3) ;
+		log::info!(target: "OWNERS", "migrate_to_v3 results migrated: {:?}", count);
+		T::DbWeight::get().reads_writes(count+1, count+1)
+	}
+
+	// Migration to v4:
+	// Owners are keyed by canonical URL, duplicates are merged into the canonical entry
+	// When both entries exist with different owners, the canonical entry is kept
+	fn migrate_to_v4() -> Weight {
+		log::info!(target: "OWNERS", "migrate_to_v4 starting");
+		let mut reads: Weight = 0 ;
+		let mut count: Weight = 0 ;
+		let mut duplicates: Vec<(Vec<u8>, T::AccountId, Vec<u8>)> = sp_std::vec![] ;
+		for (url, owner) in Owners::<T>::iter() {
+			reads += 1 ;
+			let canonical = canonicalize_url(&url) ;
+			if canonical!=url {
+				duplicates.push((url, owner, canonical)) ;
+			}
+		}
+		for (url, owner, canonical) in duplicates {
+			count += 1 ;
+			let expire_at = OwnersExpireAt::<T>::get(&url) ;
+			let has_expiry = OwnersExpireAt::<T>::contains_key(&url) ;
+			let proof_method = OwnershipProofs::get(&url) ;
+			Self::remove_owner(&url) ;
+			if Owners::<T>::contains_key(&canonical) {
+				log::info!(target: "OWNERS", "migrate_to_v4 dropped duplicate: {:?}", sp_std::str::from_utf8(&url));
+				continue ;
+			}
+			Self::set_owner(&canonical, &owner) ;
+			OwnershipProofs::insert(&canonical, proof_method) ;
+			if has_expiry {
+				OwnersExpireAt::<T>::insert(&canonical, expire_at) ;
+				ExpiryQueue::<T>::mutate(expire_at, |urls| Self::push_to_queue(urls, &canonical)) ;
+			}
+		}
+		StorageVersion::set(4) ;
+		log::info!(target: "OWNERS", "migrate_to_v4 urls migrated: {:?}", count);
+		T::DbWeight::get().reads_writes(reads+count.saturating_mul(4)+1, count.saturating_mul(8)+1)
 	}
 
 	fn current_block_number() -> T::BlockNumber {
@@ -940,14 +2062,34 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		// Migrate storage if needed
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = 0 ;
+			if StorageVersion::get()<1 {
+				weight += Self::migrate_to_v1() ;
+			}
+			if StorageVersion::get()<2 {
+				weight += Self::migrate_to_v2() ;
+			}
+			if StorageVersion::get()<3 {
+				weight += Self::migrate_to_v3() ;
+			}
+			if StorageVersion::get()<4 {
+				weight += Self::migrate_to_v4() ;
+			}
+			weight
+		}
+
 		// Process previous requests
 		fn on_initialize(current_block: T::BlockNumber) -> Weight {
 			log::debug!(target: "OWNERS", "on_initialize");
 			// Aggregate votes for previous requests
-			Self::aggregate_votes(current_block) ;
+			let mut weight = Self::aggregate_votes(current_block) ;
 			// Clean up history, requests, commits, reveals and results
-			Self::clean_up(current_block) ;
-			100_000
+			weight = weight.saturating_add(Self::clean_up(current_block)) ;
+			// Remove expired ownerships
+			weight = weight.saturating_add(Self::expire_ownerships(current_block)) ;
+			weight
 		}
 
 		// Test Tx
@@ -977,6 +2119,84 @@ decl_module! {
             Self::deposit_event(RawEvent::VerifierAdded(account));
         }
 
+        // Register as a verifier by reserving VerifierBond
+        #[weight = 10_000]
+        fn register_verifier(origin) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+
+			// Check that the signer is not already an active or bonded verifier
+			ensure!(!Self::is_verifier_enabled(&sender), Error::<T>::VerifierAlreadyRegistered) ;
+			ensure!(!VerifierBonds::<T>::contains_key(&sender), Error::<T>::VerifierAlreadyRegistered) ;
+
+			// Verifiers disabled by sudo or by a penalty can't enable themselves again
+			ensure!(!SuspendedVerifiers::<T>::contains_key(&sender), Error::<T>::VerifierSuspended) ;
+
+			// Reserve the bond
+			let bond = u128_to_balance::<T>(VerifierBond::get()) ;
+			T::Currency::reserve(&sender, bond).map_err(|_| Error::<T>::NotEnoughBalanceToBond)?;
+			VerifierBonds::<T>::insert(&sender, bond) ;
+
+			// Add or enable the verifier, keeping previous stats
+			if Self::is_verifier_registered(&sender) {
+				let mut verifier = Verifiers::<T>::take(&sender) ;
+				verifier.1 = true ;
+				Verifiers::<T>::insert(&sender, &verifier) ;
+			} else {
+				let current_block = <frame_system::Pallet<T>>::block_number();
+				let verifier = (current_block, true, 0, 0, 0, 0, 0, 0, 0) ;
+				Verifiers::<T>::insert(&sender, verifier);
+			}
+
+            // Emit an event that the verifier was bonded.
+            Self::deposit_event(RawEvent::VerifierBonded(sender, bond));
+        }
+
+        // Stop verifying and start the unbonding period
+        #[weight = 10_000]
+        fn unregister_verifier(origin) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+
+			// Check that the signer is bonded and not already leaving
+			ensure!(VerifierBonds::<T>::contains_key(&sender), Error::<T>::VerifierNotBonded) ;
+			ensure!(!Unbonding::<T>::contains_key(&sender), Error::<T>::VerifierAlreadyUnbonding) ;
+
+			// Disable the verifier so it stops committing
+			// It can still reveal its pending commits and its bond can still be slashed for them
+			let mut verifier = Verifiers::<T>::take(&sender) ;
+			verifier.1 = false ;
+			Verifiers::<T>::insert(&sender, &verifier) ;
+
+			// Schedule the release of the bond
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let release_at = current_block + u32_to_block::<T>(UnbondingPeriod::get()) ;
+			Unbonding::<T>::insert(&sender, release_at) ;
+
+            // Emit an event that the verifier is unbonding.
+            Self::deposit_event(RawEvent::VerifierUnbonding(sender, block_to_u32::<T>(release_at)));
+        }
+
+        // Release the bond once the unbonding period is over
+        #[weight = 10_000]
+        fn withdraw_bond(origin) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+
+			// Check that the unbonding period is over
+			ensure!(Unbonding::<T>::contains_key(&sender), Error::<T>::VerifierNotBonded) ;
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(current_block>=Unbonding::<T>::get(&sender), Error::<T>::UnbondingNotFinished) ;
+
+			// Release what is left of the bond
+			Unbonding::<T>::remove(&sender) ;
+			let bond = VerifierBonds::<T>::take(&sender) ;
+			T::Currency::unreserve(&sender, bond) ;
+
+            // Emit an event that the bond was withdrawn.
+            Self::deposit_event(RawEvent::VerifierBondWithdrawn(sender, bond));
+        }
+
         // Disable a verifier
         #[weight = 10_000]
         fn disable_verifier(origin, account: T::AccountId) {
@@ -986,13 +2206,8 @@ decl_module! {
 			// Check that account is already registered
 			ensure!(Self::is_verifier_registered(&account), Error::<T>::VerifierNotRegistered) ;
 
-			// Disable account
-			let mut verifier = Verifiers::<T>::take(&account) ;
-			verifier.1 = false ;
-			Verifiers::<T>::insert(&account, &verifier) ;
-
-            // Emit an event that new validator was added.
-            Self::deposit_event(RawEvent::VerifierDisabled(account));
+			// Disable account until sudo enables it again
+			Self::suspend_verifier(&account) ;
         }
 
         // Enable a verifier
@@ -1004,42 +2219,298 @@ decl_module! {
 			// Check that account is already in the list
 			ensure!(Self::is_verifier_registered(&account), Error::<T>::VerifierNotRegistered) ;
 
+			// Unbonding verifiers must withdraw and register again
+			ensure!(!Unbonding::<T>::contains_key(&account), Error::<T>::VerifierAlreadyUnbonding) ;
+
 			// Enable account
 			let mut verifier = Verifiers::<T>::take(&account) ;
 			verifier.1 = true ;
 			Verifiers::<T>::insert(&account, &verifier) ;
+			SuspendedVerifiers::<T>::remove(&account) ;
 
             // Emit an event that verifier was enabled.
             Self::deposit_event(RawEvent::VerifierEnabled(account));
         }
 
+        // Update the price of one URL check
+        #[weight = 10_000]
+        fn set_request_price(origin, price: u128) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(price>0, Error::<T>::InvalidRequestPrice) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			RequestPrice::set(price) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the commit, reveal and delete windows
+        // Requests already in flight keep the windows they were created with
+        #[weight = 10_000]
+        fn set_num_blocks(origin, num_blocks_to_commit: u8, num_blocks_to_reveal: u8, num_blocks_to_delete: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Each window must last at least one block
+			ensure!(num_blocks_to_commit>=1, Error::<T>::InvalidNumBlocks) ;
+			ensure!(num_blocks_to_reveal>=1, Error::<T>::InvalidNumBlocks) ;
+			ensure!(num_blocks_to_delete>=1, Error::<T>::InvalidNumBlocks) ;
+			ensure!(Self::is_unbonding_period_valid(UnbondingPeriod::get(), num_blocks_to_commit, num_blocks_to_reveal),
+				Error::<T>::InvalidUnbondingPeriod) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			NumBlocksToCommit::set(num_blocks_to_commit) ;
+			NumBlocksToReveal::set(num_blocks_to_reveal) ;
+			NumBlocksToDelete::set(num_blocks_to_delete) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the rules to approve ownership
+        #[weight = 10_000]
+        fn set_agreement(origin, prct_needed_for_agreement: u8, majority_min: u32) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new values
+			// The majority must exceed the bar, so 100 could never be reached
+			ensure!(prct_needed_for_agreement<100, Error::<T>::InvalidPercentage) ;
+			ensure!(majority_min>=1, Error::<T>::InvalidMajorityMin) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			PrctNeededForAgreement::set(prct_needed_for_agreement) ;
+			MajorityMin::set(majority_min) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the bond reserved from challengers
+        #[weight = 10_000]
+        fn set_challenge_bond(origin, bond: u128) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(bond>0, Error::<T>::InvalidChallengeBond) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			ChallengeBond::set(bond) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the number of times a request is reopened for lack of quorum
+        #[weight = 10_000]
+        fn set_max_retries(origin, max_retries: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			MaxRetries::set(max_retries) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the prct of the request fee refunded when nothing is decided
+        #[weight = 10_000]
+        fn set_refund_policy(origin, no_reveal_refund_prct: u8, inconclusive_refund_prct: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new values
+			ensure!(no_reveal_refund_prct<=100, Error::<T>::InvalidPercentage) ;
+			ensure!(inconclusive_refund_prct<=100, Error::<T>::InvalidPercentage) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			NoRevealRefundPrct::set(no_reveal_refund_prct) ;
+			InconclusiveRefundPrct::set(inconclusive_refund_prct) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the share of request fees paid to verifiers
+        #[weight = 10_000]
+        fn set_verifiers_share(origin, verifiers_share_prct: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new value
+			ensure!(verifiers_share_prct<=100, Error::<T>::InvalidPercentage) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			VerifiersSharePrct::set(verifiers_share_prct) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
+        // Update the verifier staking parameters
+        // Verifiers already registered keep their current bond
+        #[weight = 10_000]
+        fn set_staking(origin, verifier_bond: u128, unbonding_period: u32, slash_prct: u8, max_minority_prct: u8, max_missed_reveals_prct: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new values
+			ensure!(verifier_bond>0, Error::<T>::InvalidVerifierBond) ;
+			ensure!(Self::is_unbonding_period_valid(unbonding_period, NumBlocksToCommit::get(), NumBlocksToReveal::get()),
+				Error::<T>::InvalidUnbondingPeriod) ;
+			ensure!(slash_prct<=100, Error::<T>::InvalidPercentage) ;
+			ensure!(max_minority_prct<=100, Error::<T>::InvalidPercentage) ;
+			ensure!(max_missed_reveals_prct<=100, Error::<T>::InvalidPercentage) ;
+
+			// Update and emit the change
+			let old = Self::get_staking_parameters() ;
+			VerifierBond::set(verifier_bond) ;
+			UnbondingPeriod::set(unbonding_period) ;
+			SlashPrct::set(slash_prct) ;
+			MaxMinorityPrct::set(max_minority_prct) ;
+			MaxMissedRevealsPrct::set(max_missed_reveals_prct) ;
+			Self::deposit_event(RawEvent::StakingParametersUpdated(old, Self::get_staking_parameters()));
+        }
+
+        // Update the ownership period and the renewal discount
+        // Ownerships already registered keep their expiry block
+        #[weight = 10_000]
+        fn set_ownership_ttl(origin, ownership_ttl: u32, renew_price_prct: u8) {
+            // Check that the extrinsic is from sudo.
+            ensure_root(origin)?;
+
+			// Check the new values
+			ensure!(renew_price_prct<=100, Error::<T>::InvalidPercentage) ;
+
+			// Update and emit the change
+			let old = Self::get_parameters() ;
+			OwnershipTtl::set(ownership_ttl) ;
+			RenewPricePrct::set(renew_price_prct) ;
+			Self::deposit_event(RawEvent::ParametersUpdated(old, Self::get_parameters()));
+        }
+
         // Trigger a new url check
+        // The proof method tells verifiers where to look for the requester's address
+        #[weight = 10_000]
+        fn request_url_check(origin, url: Vec<u8>, proof_method: ProofMethod) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+			let url = canonicalize_url(&url) ;
+
+			// URLs owned by someone else must be challenged first
+			ensure!(!Owners::<T>::contains_key(&url) || Self::is_url_owner(&url, &sender), Error::<T>::UrlAlreadyOwned) ;
+
+			// Prefixes must follow a host and be proven for the whole domain
+			if is_url_prefix(&url) {
+				ensure!(url_origin(&url).is_some(), Error::<T>::InvalidUrlPrefix) ;
+				let domain_proof = proof_method==ProofMethod::WellKnown || proof_method==ProofMethod::DnsTxt ;
+				ensure!(domain_proof, Error::<T>::PrefixNeedsDomainProof) ;
+			}
+
+			// Pay and insert the URL in the check request queue
+			let price = RequestPrice::get() ;
+			Self::submit_request(&sender, &url, price, &sender, proof_method, Error::<T>::NotEnoughBalanceToRequestUrlCheck)?;
+
+            // Emit an event that UrlCheckRequest was recorded.
+            Self::deposit_event(RawEvent::UrlCheckRequested(sender, url));
+        }
+
+        // Re-verify an owned URL at a discounted price to start a new ownership period
+        #[weight = 10_000]
+        fn renew_ownership(origin, url: Vec<u8>) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+			let url = canonicalize_url(&url) ;
+
+			// Check that the signer owns the URL
+			ensure!(Self::is_url_owner(&url, &sender), Error::<T>::NotUrlOwner) ;
+
+			// Pay the discounted price and insert the URL in the check request queue
+			let prct: u128 = RenewPricePrct::get().into() ;
+			let price = RequestPrice::get().saturating_mul(prct) / 100 ;
+			Self::submit_request(&sender, &url, price, &sender, OwnershipProofs::get(&url), Error::<T>::NotEnoughBalanceToRenewOwnership)?;
+
+            // Emit an event that the renewal was requested.
+            Self::deposit_event(RawEvent::OwnershipRenewalRequested(sender, url));
+        }
+
+        // Dispute the current owner of a URL
+        // Verifiers check the page for the owner's mark:
+        // - mark found: the challenger's bond is sent to the pot
+        // - mark not found: the owner is removed and the bond is released
+        // - no agreement: the bond is released
         #[weight = 10_000]
-        fn request_url_check(origin, url: Vec<u8>) {
+        #[transactional]
+        fn challenge_ownership(origin, url: Vec<u8>) {
             // Check that the extrinsic was signed and get the signer.
             let sender = ensure_signed(origin)?;
+			let url = canonicalize_url(&url) ;
 
-			// Check URL length
-			ensure!(url.len()<T::MaxLengthURL::get().into(), Error::<T>::UrlTooLong) ;
+			// Check that the URL has an owner other than the signer
+			ensure!(Owners::<T>::contains_key(&url), Error::<T>::UrlNotOwned) ;
+			let owner = Owners::<T>::get(&url) ;
+			ensure!(owner!=sender, Error::<T>::CantChallengeOwnUrl) ;
 
-			// Check that the signer has enough funds to be sent to the pot
+			// Check that the signer can afford the bond on top of the request price
+			let bond = u128_to_balance::<T>(ChallengeBond::get()) ;
 			let price = u128_to_balance::<T>(RequestPrice::get()) ;
 			let free = T::Currency::free_balance(&sender) ;
-			ensure!(free>price, Error::<T>::NotEnoughBalanceToRequestUrlCheck) ;
+			let needed = price.saturating_add(bond).saturating_add(T::Currency::minimum_balance()) ;
+			ensure!(free>=needed, Error::<T>::NotEnoughBalanceToChallenge) ;
+
+			// Reserve the bond
+			T::Currency::reserve(&sender, bond).map_err(|_| Error::<T>::NotEnoughBalanceToChallenge)?;
+
+			// Pay and insert the URL in the check request queue, looking for the owner's mark
+			// The bond is released if the request can't be submitted
+			Self::submit_request(&sender, &url, RequestPrice::get(), &owner, OwnershipProofs::get(&url), Error::<T>::NotEnoughBalanceToChallenge)?;
+			Challenges::<T>::insert(&url, (&sender, bond)) ;
+
+            // Emit an event that the ownership was challenged.
+            Self::deposit_event(RawEvent::OwnershipChallenged(sender, owner, url));
+        }
 
-			// Check that that this URL is not already in the queue
+        // Transfer the ownership of a URL to another account
+        #[weight = 10_000]
+        fn transfer_ownership(origin, url: Vec<u8>, new_owner: T::AccountId) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+			let url = canonicalize_url(&url) ;
+
+			// Check that the signer owns the URL
+			ensure!(Self::is_url_owner(&url, &sender), Error::<T>::NotUrlOwner) ;
+
+			// Ownership can't be moved away while it is being challenged
+			ensure!(!Challenges::<T>::contains_key(&url), Error::<T>::OwnershipChallenged) ;
+
+			// Nor while a renewal is being verified, its outcome would apply to the new owner
 			ensure!(!Self::is_url_being_verified(&url), Error::<T>::UrlCheckAlreadyInQueue) ;
 
-			// Send check price to pot
-			Self::send_to_pot(&sender, price) ;
+			// Register the new owner
+			Self::set_owner(&url, &new_owner) ;
 
-			// Insert the URL in the check request queue at current block
-			let current_block = <frame_system::Pallet<T>>::block_number();
-			Self::create_request(&current_block, &url, &sender) ;
-			log::debug!(target: "OWNERS", "request_url_check inserted at block: {:?}", &current_block);
+            // Emit an event that the ownership was transferred.
+            Self::deposit_event(RawEvent::OwnershipTransferred(sender, new_owner, url));
+        }
 
-            // Emit an event that UrlCheckRequest was recorded.
-            Self::deposit_event(RawEvent::UrlCheckRequested(sender, url));
+        // Give up the ownership of a URL
+        #[weight = 10_000]
+        fn renounce_ownership(origin, url: Vec<u8>) {
+            // Check that the extrinsic was signed and get the signer.
+            let sender = ensure_signed(origin)?;
+			let url = canonicalize_url(&url) ;
+
+			// Check that the signer owns the URL
+			ensure!(Self::is_url_owner(&url, &sender), Error::<T>::NotUrlOwner) ;
+
+			// Pending challenges and renewals must be settled first
+			ensure!(!Challenges::<T>::contains_key(&url), Error::<T>::OwnershipChallenged) ;
+			ensure!(!Self::is_url_being_verified(&url), Error::<T>::UrlCheckAlreadyInQueue) ;
+
+			// Remove the owner, likes will go to the pot again
+			Self::remove_owner(&url) ;
+
+            // Emit an event that the ownership was renounced.
+            Self::deposit_event(RawEvent::OwnershipRenounced(sender, url));
         }
 
         // Receive commits from verfiers
@@ -1063,9 +2534,9 @@ decl_module! {
 
 			// Check that it's a good time to receive commits
 			let current_block = Self::current_block_number() ;
-			let request_block = Requests::<T>::get(&url).0 ;
-			let param = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-			let max_block = request_block + param ;
+			let request = Requests::<T>::get(&url) ;
+			let request_block = request.0 ;
+			let max_block = Self::commit_deadline(&request) ;
 			let timing_ok = current_block>request_block && current_block<=max_block ;
 			log::debug!(target: "OWNERS", "commit_verification current_block: {:?}", &current_block);
 			log::debug!(target: "OWNERS", "commit_verification request_block: {:?}", &request_block);
@@ -1100,36 +2571,32 @@ decl_module! {
         	// Check that the extrinsic was signed and get the signer.
             let sender = ensure_signed(origin)?;
 
-			// Check that the signer is an enabled verifier
-			ensure!(Self::is_verifier_enabled(&sender), Error::<T>::VerifierNotRegistered) ;
+			// Check that the signer is an enabled or unbonding verifier
+			ensure!(Self::can_verifier_reveal(&sender), Error::<T>::VerifierNotRegistered) ;
 
 			// Check that the request exists in the queue
 			ensure!(Requests::<T>::contains_key(&url), Error::<T>::UrlCheckNotFound) ;
 			let request = Requests::<T>::get(&url) ;
-			let request_block = request.0 ;
-			let request_account = request.1 ;
+			let request_account = request.1.clone() ;
 
 			// Check intro length
-			ensure!(intro.len()==INTRO_LENGTH, Error::<T>::InvalidProofOfOwnership) ;
+			ensure!(intro.len()<=INTRO_LENGTH, Error::<T>::InvalidProofOfOwnership) ;
 
 			// If vote is positive
 			if vote {
-				// Check that the proof has the correct length
-				ensure!(proof.len()==MARK_LENGTH, Error::<T>::InvalidProofOfOwnership) ;
+				// Check that the proof is not longer than a mark
+				ensure!(proof.len()<=MARK_LENGTH, Error::<T>::InvalidProofOfOwnership) ;
 
-				// Check that the proof has cocontains the account address
+				// Check that the proof matches the request proof method and contains the account address
 				log::debug!(target: "OWNERS", "reveal_verification request_account: {:?}", &request_account);
 				log::debug!(target: "OWNERS", "reveal_verification proof: {:?}", &proof);
-				// TODO
-				ensure!(true, Error::<T>::InvalidProofOfOwnership) ;
+				ensure!(proof_matches_account(RequestProofs::get(&url), &proof, &request_account), Error::<T>::InvalidProofOfOwnership) ;
 			}
 
 			// Check that it's a good time to receive reveals
 			let current_block = <frame_system::Pallet<T>>::block_number();
-			let param1 = u8_to_block::<T>(NumBlocksToCommit::get()) ;
-			let param2 = u8_to_block::<T>(NumBlocksToReveal::get()) ;
-			let min_block = request_block + param1 ;
-			let max_block = request_block + param1 + param2 ;
+			let min_block = Self::commit_deadline(&request) ;
+			let max_block = Self::reveal_deadline(&request) ;
 			let timing_ok = current_block>min_block && current_block<=max_block ;
 			log::debug!(target: "OWNERS", "reveal_verification current_block: {:?}", &current_block);
 			log::debug!(target: "OWNERS", "reveal_verification min_block: {:?}", &min_block);
@@ -1195,23 +2662,25 @@ decl_module! {
 			// Check verifier status
 			log::debug!(target: "OWNERS", "offchain_worker checking node account");
 
-			let account = Self::get_local_verifier() ;
+			let account = Self::get_local_verifier(Self::can_verifier_reveal) ;
 			if account.is_none() {
 				log::debug!(target: "OWNERS", "offchain_worker is OFF");
 				return ;
 			}
 			log::debug!(target: "OWNERS", "offchain_worker is ON");
 
-			// Process URL checks and send commits
-			log::debug!(target: "OWNERS", "offchain_worker *** processing checks and commits ***");
-			let requests = History::<T>::get(block_number) ;
-			for url in requests.iter() {
-				let request = Requests::<T>::get(&url) ;
-				let requested_at = request.0 ;
-				let requester = request.1 ;
-				Self::check_url_offchain(&url, &requester, requested_at) ;
+			// Process URL checks and send commits, unbonding verifiers only reveal
+			if Self::get_local_verifier(Self::is_verifier_enabled).is_some() {
+				log::debug!(target: "OWNERS", "offchain_worker *** processing checks and commits ***");
+				let requests = History::<T>::get(block_number) ;
+				for url in requests.iter() {
+					let request = Requests::<T>::get(&url) ;
+					let commit_deadline = Self::commit_deadline(&request) ;
+					let requester = request.1 ;
+					Self::check_url_offchain(&url, &requester, commit_deadline, RequestProofs::get(&url)) ;
+				}
+				log::debug!(target: "OWNERS", "offchain_worker *** checks and commits DONE ***");
 			}
-			log::debug!(target: "OWNERS", "offchain_worker *** checks and commits DONE ***");
 
 			// Send reveals
 			log::debug!(target: "OWNERS", "offchain_worker *** processing reveals ***");
@@ -1235,4 +2704,3 @@ decl_module! {
 
 	}
 }
-
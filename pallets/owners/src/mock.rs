@@ -0,0 +1,180 @@
+use crate as pallet_owners ;
+use frame_support::{parameter_types, traits::{AllowAll, OnInitialize}, weights::constants::RocksDbWeight} ;
+use sp_core::{H256, Pair, sr25519} ;
+use sp_runtime::{
+	BuildStorage,
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup}
+} ;
+use sp_std::vec::Vec ;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test> ;
+type Block = frame_system::mocking::MockBlock<Test> ;
+type Extrinsic = TestXt<Call, ()> ;
+
+// Reveals check the salt against the sender's sr25519 key,
+// so mock accounts are sr25519 public keys
+pub type AccountId = sr25519::Public ;
+
+// Seeds of the mock keys
+pub const ALICE: u8 = 1 ;
+pub const BOB: u8 = 2 ;
+pub const CHARLIE: u8 = 3 ;
+pub const VERIFIER_1: u8 = 11 ;
+pub const VERIFIER_2: u8 = 12 ;
+pub const VERIFIER_3: u8 = 13 ;
+
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000_000 ;
+pub const REQUEST_PRICE: u128 = 5_000_000_000_000 ;
+pub const CHALLENGE_BOND: u128 = 10_000_000_000_000 ;
+pub const VERIFIER_BOND: u128 = 100_000_000_000_000 ;
+pub const UNBONDING_PERIOD: u32 = 10 ;
+
+// URL owned by ALICE at genesis
+pub const OWNED_URL: &[u8] = b"https://example.com/owned" ;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Owners: pallet_owners::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+) ;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250 ;
+	pub const SS58Prefix: u8 = 42 ;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = AllowAll ;
+	type BlockWeights = () ;
+	type BlockLength = () ;
+	// on_initialize reports its weight in database accesses
+	type DbWeight = RocksDbWeight ;
+	type Origin = Origin ;
+	type Call = Call ;
+	type Index = u64 ;
+	type BlockNumber = u64 ;
+	type Hash = H256 ;
+	type Hashing = BlakeTwo256 ;
+	type AccountId = AccountId ;
+	type Lookup = IdentityLookup<Self::AccountId> ;
+	type Header = Header ;
+	type Event = Event ;
+	type BlockHashCount = BlockHashCount ;
+	type Version = () ;
+	type PalletInfo = PalletInfo ;
+	type AccountData = pallet_balances::AccountData<u128> ;
+	type OnNewAccount = () ;
+	type OnKilledAccount = () ;
+	type SystemWeightInfo = () ;
+	type SS58Prefix = SS58Prefix ;
+	type OnSetCode = () ;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1 ;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = () ;
+	type MaxReserves = () ;
+	type ReserveIdentifier = [u8; 8] ;
+	type Balance = u128 ;
+	type Event = Event ;
+	type DustRemoval = () ;
+	type ExistentialDeposit = ExistentialDeposit ;
+	type AccountStore = System ;
+	type WeightInfo = () ;
+}
+
+// Offchain transactions are never submitted by the tests,
+// these only satisfy CreateSignedTransaction
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = sr25519::Public ;
+	type Signature = sr25519::Signature ;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test where Call: From<LocalCall> {
+	type OverarchingCall = Call ;
+	type Extrinsic = Extrinsic ;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test where Call: From<LocalCall> {
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: sr25519::Public,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub const MaxLengthURL: u8 = 255 ;
+	pub const NumChecksRequired: u8 = 2 ;
+}
+
+impl pallet_owners::Config for Test {
+	type OwnersAppCrypto = pallet_owners::crypto::OwnersAppCrypto ;
+	type OwnersPublic = pallet_owners::crypto::Public ;
+	type Call = Call ;
+	type Event = Event ;
+	type Currency = Balances ;
+	type MaxLengthURL = MaxLengthURL ;
+	type NumChecksRequired = NumChecksRequired ;
+	type VoteWeighting = pallet_owners::EqualWeight ;
+}
+
+// Key pair derived from a seed
+pub fn pair(seed: u8) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[seed; 32])
+}
+
+// Account of the key pair derived from a seed
+pub fn account(seed: u8) -> AccountId {
+	pair(seed).public()
+}
+
+// Build genesis storage with short commit, reveal and delete windows (2 blocks each),
+// three verifiers added without a bond and OWNED_URL owned by ALICE
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let funded: Vec<u8> = sp_std::vec![ALICE, BOB, CHARLIE, VERIFIER_1, VERIFIER_2, VERIFIER_3] ;
+	let storage = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig {
+			balances: funded.iter().map(|seed| (account(*seed), INITIAL_BALANCE)).collect(),
+		},
+		owners: OwnersConfig {
+			request_price: REQUEST_PRICE,
+			num_blocks_to_commit: 2,
+			num_blocks_to_reveal: 2,
+			num_blocks_to_delete: 2,
+			challenge_bond: CHALLENGE_BOND,
+			verifier_bond: VERIFIER_BOND,
+			unbonding_period: UNBONDING_PERIOD,
+			verifiers: sp_std::vec![account(VERIFIER_1), account(VERIFIER_2), account(VERIFIER_3)],
+			owners: sp_std::vec![(OWNED_URL.to_vec(), account(ALICE))],
+			..Default::default()
+		},
+	}.build_storage().unwrap() ;
+	let mut ext = sp_io::TestExternalities::new(storage) ;
+	// Events are only recorded from block 1
+	ext.execute_with(|| System::set_block_number(1)) ;
+	ext
+}
+
+// Run on_initialize for every block up to n
+pub fn run_to_block(n: u64) {
+	while System::block_number()<n {
+		let next = System::block_number() + 1 ;
+		System::set_block_number(next) ;
+		Owners::on_initialize(next) ;
+	}
+}
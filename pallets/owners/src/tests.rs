@@ -0,0 +1,541 @@
+use crate::{mock::*, Error, RawEvent, ProofMethod, Requests, Verifiers, VerifierBonds, Unbonding,
+	truncate_str, extract_page_parts, mark_matches_account, proof_matches_account, account_to_hex,
+	extract_meta_author, proof_location, doh_resolver, DOH_URL, DOH_URL_KEY,
+	INTRO_LENGTH, MARK_LENGTH, MARK_PREFIX, DNS_TXT_PREFIX, RESULT_STATE_DECIDED, RESULT_STATE_INCONCLUSIVE} ;
+use frame_support::{assert_noop, assert_ok, traits::{Currency, OnInitialize, ReservableCurrency}} ;
+use parity_scale_codec::Encode ;
+use sp_core::{Pair, offchain::{OffchainDbExt, StorageKind, testing::TestOffchainExt}} ;
+use sp_io::hashing::keccak_256 ;
+use wika_traits::OwnershipRegistry ;
+
+// Last event deposited by the owners pallet
+fn last_event() -> crate::Event<Test> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			Event::Owners(event) => Some(event),
+			_ => None
+		})
+		.last()
+		.expect("an owners event was deposited")
+}
+
+// Intro revealed by every verifier
+const INTRO: &[u8] = b"<html>" ;
+
+// Proof revealed by verifiers who found the requester's mark
+fn mark_of(account: &AccountId) -> Vec<u8> {
+	let mut mark: Vec<u8> = MARK_PREFIX.into() ;
+	mark.extend_from_slice(&account_to_hex(account).unwrap()) ;
+	mark
+}
+
+// Commit the votes of verifiers for the pending request of a URL, reveal them
+// and run until they are aggregated
+// A vote is the seed of a verifier and whether it found the requester's mark
+fn verify(url: &[u8], votes: &[(u8, bool)]) {
+	let revealed: Vec<u8> = votes.iter().map(|(seed, _)| *seed).collect() ;
+	verify_revealing(url, votes, &revealed) ;
+}
+
+// Same as verify, only the verifiers listed in revealed reveal their vote
+fn verify_revealing(url: &[u8], votes: &[(u8, bool)], revealed: &[u8]) {
+	let url = url.to_vec() ;
+	let request = Requests::<Test>::get(&url) ;
+	let intro = INTRO.to_vec() ;
+	let mut ballots = sp_std::vec![] ;
+	run_to_block(request.0+1) ;
+	for (seed, vote) in votes {
+		let proof = if *vote { mark_of(&request.1) } else { sp_std::vec![] } ;
+		let proof_option = if *vote { Some(&proof) } else { None } ;
+		let salt = pair(*seed).sign(&Owners::concat_data1(*vote, &intro, proof_option)).encode() ;
+		let hash = keccak_256(&Owners::concat_data2(*vote, &intro, proof_option, &salt)) ;
+		assert_ok!(Owners::commit_verification(Origin::signed(account(*seed)), url.clone(), hash.to_vec())) ;
+		ballots.push((*seed, *vote, proof, salt)) ;
+	}
+	let commit_deadline = request.0 + u64::from(request.2) ;
+	run_to_block(commit_deadline+1) ;
+	for (seed, vote, proof, salt) in ballots {
+		if revealed.contains(&seed) {
+			assert_ok!(Owners::reveal_verification(Origin::signed(account(seed)), url.clone(), vote, intro.clone(), proof, salt)) ;
+		}
+	}
+	run_to_block(commit_deadline + u64::from(request.3) + 1) ;
+}
+
+
+#[test]
+fn truncate_str_keeps_short_text() {
+	assert_eq!(truncate_str("", 4), "") ;
+	assert_eq!(truncate_str("abcd", 4), "abcd") ;
+	assert_eq!(truncate_str("abcde", 4), "abcd") ;
+}
+
+#[test]
+fn truncate_str_cuts_on_char_boundary() {
+	// é is 2 bytes, 😀 is 4 bytes
+	assert_eq!(truncate_str("héllo", 2), "h") ;
+	assert_eq!(truncate_str("héllo", 3), "hé") ;
+	assert_eq!(truncate_str("a😀", 4), "a") ;
+	assert_eq!(truncate_str("a😀", 5), "a😀") ;
+	assert_eq!(truncate_str("😀", 3), "") ;
+}
+
+#[test]
+fn extract_page_parts_finds_mark_in_comment() {
+	let page = b"<html><head><!-- wika.network/author/00ff --></head><body></body></html>" ;
+	let (_, mark) = extract_page_parts(page) ;
+	assert_eq!(mark, Some(b"wika.network/author/00ff".to_vec())) ;
+}
+
+#[test]
+fn extract_page_parts_finds_mark_in_cdata() {
+	let page = b"<script><![CDATA[var a = 'wika.network/author/abc123';]]></script>" ;
+	let (_, mark) = extract_page_parts(page) ;
+	assert_eq!(mark, Some(b"wika.network/author/abc123".to_vec())) ;
+}
+
+#[test]
+fn extract_page_parts_handles_uppercase_tags_and_unquoted_attributes() {
+	let page = b"<HTML><BODY><A HREF=https://wika.network/author/ABCdef09>Me</A></BODY></HTML>" ;
+	let (intro, mark) = extract_page_parts(page) ;
+	assert_eq!(intro, page.to_vec()) ;
+	assert_eq!(mark, Some(b"wika.network/author/ABCdef09".to_vec())) ;
+}
+
+#[test]
+fn extract_page_parts_handles_missing_head_end() {
+	let page = b"<html><head><title>Post</title><body><p>wika.network/author/1234</p></body>" ;
+	let (_, mark) = extract_page_parts(page) ;
+	assert_eq!(mark, Some(b"wika.network/author/1234".to_vec())) ;
+}
+
+#[test]
+fn extract_page_parts_without_mark() {
+	let page = b"<html><body>wika.network/about</body></html>" ;
+	let (intro, mark) = extract_page_parts(page) ;
+	assert_eq!(intro, page.to_vec()) ;
+	assert_eq!(mark, None) ;
+}
+
+#[test]
+fn extract_page_parts_cuts_multibyte_intro_at_limit() {
+	// 1 + 2*100 bytes, the limit falls in the middle of an é
+	let page = format!("a{}", "é".repeat(100)) ;
+	let (intro, _) = extract_page_parts(page.as_bytes()) ;
+	assert_eq!(intro.len(), INTRO_LENGTH-1) ;
+	assert_eq!(String::from_utf8(intro).unwrap(), format!("a{}", "é".repeat(63))) ;
+}
+
+#[test]
+fn extract_page_parts_replaces_invalid_utf8() {
+	let page = b"<p>\xff\xfe</p>wika.network/author/ab" ;
+	let (intro, mark) = extract_page_parts(page) ;
+	assert!(String::from_utf8(intro).is_ok()) ;
+	assert_eq!(mark, Some(b"wika.network/author/ab".to_vec())) ;
+}
+
+#[test]
+fn mark_matches_account_requires_exact_address() {
+	let account = [0xabu8; 32] ;
+	let address = "ab".repeat(32) ;
+	let mark = format!("{}{}", MARK_PREFIX, address) ;
+	assert!(mark_matches_account(mark.as_bytes(), &account)) ;
+	assert!(mark_matches_account(mark.to_uppercase().replace("WIKA.NETWORK/AUTHOR/", MARK_PREFIX).as_bytes(), &account)) ;
+	assert!(!mark_matches_account(format!("{}x", mark).as_bytes(), &account)) ;
+	assert!(!mark_matches_account(format!("{}00{}", MARK_PREFIX, address).as_bytes(), &account)) ;
+	assert!(!mark_matches_account(address.as_bytes(), &account)) ;
+	assert!(!mark_matches_account(format!("{}{}", MARK_PREFIX, "cd".repeat(32)).as_bytes(), &account)) ;
+}
+
+#[test]
+fn proof_matches_account_by_method() {
+	let account = [0x01u8; 32] ;
+	let address = "01".repeat(32) ;
+	assert!(proof_matches_account(ProofMethod::MetaTag, address.as_bytes(), &account)) ;
+	assert!(proof_matches_account(ProofMethod::WellKnown, address.as_bytes(), &account)) ;
+	assert!(!proof_matches_account(ProofMethod::WellKnown, format!("0x{}", address).as_bytes(), &account)) ;
+	assert!(proof_matches_account(ProofMethod::DnsTxt, format!("{}{}", DNS_TXT_PREFIX, address).as_bytes(), &account)) ;
+	assert!(!proof_matches_account(ProofMethod::DnsTxt, address.as_bytes(), &account)) ;
+}
+
+#[test]
+fn extract_page_parts_truncates_long_mark() {
+	let page = format!("{}{}", MARK_PREFIX, "a".repeat(200)) ;
+	let (_, mark) = extract_page_parts(page.as_bytes()) ;
+	assert_eq!(mark.map(|mark| mark.len()), Some(MARK_LENGTH)) ;
+}
+
+#[test]
+fn extract_meta_author_accepts_both_quotes() {
+	assert_eq!(extract_meta_author("<meta name=\"wika-author\" content=\"abc\">"), Some("abc")) ;
+	assert_eq!(extract_meta_author("<meta content='abc' name='wika-author'>"), Some("abc")) ;
+	assert_eq!(extract_meta_author("<meta name=\"wika-author\" content='it\"s'>"), Some("it\"s")) ;
+	assert_eq!(extract_meta_author("<meta name='author' content='abc'>"), None) ;
+}
+
+#[test]
+fn proof_location_appends_the_host_to_the_resolver() {
+	let url = b"https://blog.example.com:8080/post".to_vec() ;
+	let (location, _) = proof_location(&url, ProofMethod::DnsTxt, b"https://dns.example/q?name=").unwrap() ;
+	assert_eq!(location, b"https://dns.example/q?name=blog.example.com".to_vec()) ;
+}
+
+#[test]
+fn doh_resolver_is_read_from_offchain_storage() {
+	let (offchain, _) = TestOffchainExt::new() ;
+	let mut ext = sp_io::TestExternalities::default() ;
+	ext.register_extension(OffchainDbExt::new(offchain)) ;
+	ext.execute_with(|| {
+		assert_eq!(doh_resolver(), DOH_URL.to_vec()) ;
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, DOH_URL_KEY, b"https://dns.example/q?name=") ;
+		assert_eq!(doh_resolver(), b"https://dns.example/q?name=".to_vec()) ;
+	}) ;
+}
+
+#[test]
+fn transfer_ownership_works() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::transfer_ownership(Origin::signed(account(ALICE)), url.clone(), account(BOB))) ;
+		assert_eq!(Owners::get_url_owner(&url), Some(account(BOB))) ;
+		assert_eq!(Owners::get_owned_urls(&account(ALICE)), Vec::<Vec<u8>>::new()) ;
+		assert_eq!(Owners::get_owned_urls(&account(BOB)), vec![url.clone()]) ;
+		assert_eq!(last_event(), RawEvent::OwnershipTransferred(account(ALICE), account(BOB), url)) ;
+	}) ;
+}
+
+#[test]
+fn transfer_ownership_requires_owner() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Owners::transfer_ownership(Origin::signed(account(BOB)), OWNED_URL.to_vec(), account(BOB)),
+			Error::<Test>::NotUrlOwner) ;
+	}) ;
+}
+
+#[test]
+fn renounce_ownership_works() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::renounce_ownership(Origin::signed(account(ALICE)), url.clone())) ;
+		assert_eq!(Owners::get_url_owner(&url), None) ;
+		assert_eq!(Owners::get_owned_urls(&account(ALICE)), Vec::<Vec<u8>>::new()) ;
+		assert_eq!(last_event(), RawEvent::OwnershipRenounced(account(ALICE), url)) ;
+	}) ;
+}
+
+#[test]
+fn ownership_is_locked_while_a_renewal_is_verified() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::renew_ownership(Origin::signed(account(ALICE)), url.clone())) ;
+		assert_noop!(Owners::transfer_ownership(Origin::signed(account(ALICE)), url.clone(), account(BOB)),
+			Error::<Test>::UrlCheckAlreadyInQueue) ;
+		assert_noop!(Owners::renounce_ownership(Origin::signed(account(ALICE)), url.clone()),
+			Error::<Test>::UrlCheckAlreadyInQueue) ;
+
+		// Nobody votes, the request is retried once then cleaned up
+		run_to_block(30) ;
+		assert!(!Requests::<Test>::contains_key(&url)) ;
+		assert_ok!(Owners::transfer_ownership(Origin::signed(account(ALICE)), url.clone(), account(BOB))) ;
+	}) ;
+}
+
+#[test]
+fn ownership_is_locked_while_challenged() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::challenge_ownership(Origin::signed(account(BOB)), url.clone())) ;
+		assert_noop!(Owners::transfer_ownership(Origin::signed(account(ALICE)), url.clone(), account(CHARLIE)),
+			Error::<Test>::OwnershipChallenged) ;
+		assert_noop!(Owners::renounce_ownership(Origin::signed(account(ALICE)), url),
+			Error::<Test>::OwnershipChallenged) ;
+	}) ;
+}
+
+#[test]
+fn request_url_check_keeps_the_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		let url = b"https://example.com/new".to_vec() ;
+		let _ = Balances::make_free_balance_be(&account(CHARLIE), REQUEST_PRICE) ;
+		assert_noop!(Owners::request_url_check(Origin::signed(account(CHARLIE)), url.clone(), ProofMethod::Body),
+			Error::<Test>::NotEnoughBalanceToRequestUrlCheck) ;
+		let _ = Balances::make_free_balance_be(&account(CHARLIE), REQUEST_PRICE+ExistentialDeposit::get()) ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(CHARLIE)), url, ProofMethod::Body)) ;
+		assert_eq!(Balances::free_balance(&account(CHARLIE)), ExistentialDeposit::get()) ;
+	}) ;
+}
+
+#[test]
+fn on_initialize_weight_grows_with_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), b"https://example.com/new".to_vec(), ProofMethod::Body)) ;
+		// Requested at block 1, aggregated at block 6 after 2 blocks of commits and 2 of reveals
+		let idle = Owners::on_initialize(5) ;
+		let busy = Owners::on_initialize(6) ;
+		assert!(idle>0) ;
+		assert!(busy>idle) ;
+	}) ;
+}
+
+#[test]
+fn challenge_bond_goes_to_the_pot_when_the_mark_is_found() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::challenge_ownership(Origin::signed(account(BOB)), url.clone())) ;
+		assert_eq!(Balances::reserved_balance(&account(BOB)), CHALLENGE_BOND) ;
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, true), (VERIFIER_3, true)]) ;
+		assert_eq!(last_event(), RawEvent::ChallengeRejected(account(BOB), account(ALICE), url.clone())) ;
+		assert_eq!(Owners::get_url_owner(&url), Some(account(ALICE))) ;
+		assert_eq!(Balances::reserved_balance(&account(BOB)), 0) ;
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE-CHALLENGE_BOND) ;
+		// The pot keeps the bond and what is left of the fee once verifiers are paid
+		let paid = REQUEST_PRICE/2/3 ;
+		assert_eq!(Balances::free_balance(&Owners::get_pot_id()), REQUEST_PRICE-3*paid+CHALLENGE_BOND) ;
+	}) ;
+}
+
+#[test]
+fn challenge_bond_is_released_when_the_mark_is_gone() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::challenge_ownership(Origin::signed(account(BOB)), url.clone())) ;
+		verify(&url, &[(VERIFIER_1, false), (VERIFIER_2, false), (VERIFIER_3, false)]) ;
+		assert_eq!(last_event(), RawEvent::ChallengeAccepted(account(BOB), account(ALICE), url.clone())) ;
+		assert_eq!(Owners::get_url_owner(&url), None) ;
+		assert_eq!(Balances::reserved_balance(&account(BOB)), 0) ;
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE) ;
+	}) ;
+}
+
+#[test]
+fn challenge_bond_is_released_without_agreement() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::challenge_ownership(Origin::signed(account(BOB)), url.clone())) ;
+		// 50% of the votes doesn't pass the 66% bar
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, false)]) ;
+		assert_eq!(last_event(), RawEvent::ChallengeInconclusive(account(BOB), url.clone())) ;
+		assert_eq!(Owners::get_url_owner(&url), Some(account(ALICE))) ;
+		assert_eq!(Balances::reserved_balance(&account(BOB)), 0) ;
+		// Inconclusive requests refund half of the fee by default
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE+REQUEST_PRICE/2) ;
+	}) ;
+}
+
+#[test]
+fn challenge_bond_is_not_reserved_when_the_request_fails() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		assert_ok!(Owners::renew_ownership(Origin::signed(account(ALICE)), url.clone())) ;
+		assert_noop!(Owners::challenge_ownership(Origin::signed(account(BOB)), url),
+			Error::<Test>::UrlCheckAlreadyInQueue) ;
+		assert_eq!(Balances::reserved_balance(&account(BOB)), 0) ;
+	}) ;
+}
+
+#[test]
+fn challenge_requires_the_bond_and_the_price() {
+	new_test_ext().execute_with(|| {
+		let url = OWNED_URL.to_vec() ;
+		let _ = Balances::make_free_balance_be(&account(BOB), REQUEST_PRICE+CHALLENGE_BOND) ;
+		assert_noop!(Owners::challenge_ownership(Origin::signed(account(BOB)), url.clone()),
+			Error::<Test>::NotEnoughBalanceToChallenge) ;
+		let _ = Balances::make_free_balance_be(&account(BOB), REQUEST_PRICE+CHALLENGE_BOND+ExistentialDeposit::get()) ;
+		assert_ok!(Owners::challenge_ownership(Origin::signed(account(BOB)), url)) ;
+	}) ;
+}
+
+// Register CHARLIE as a bonded verifier
+fn register_charlie() {
+	assert_ok!(Owners::register_verifier(Origin::signed(account(CHARLIE)))) ;
+}
+
+#[test]
+fn register_verifier_reserves_the_bond() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		assert_eq!(last_event(), RawEvent::VerifierBonded(account(CHARLIE), VERIFIER_BOND)) ;
+		assert!(Verifiers::<Test>::get(&account(CHARLIE)).1) ;
+		assert_eq!(VerifierBonds::<Test>::get(&account(CHARLIE)), VERIFIER_BOND) ;
+		assert_eq!(Balances::reserved_balance(&account(CHARLIE)), VERIFIER_BOND) ;
+		assert_noop!(Owners::register_verifier(Origin::signed(account(CHARLIE))), Error::<Test>::VerifierAlreadyRegistered) ;
+	}) ;
+}
+
+#[test]
+fn register_verifier_requires_the_bond() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::make_free_balance_be(&account(CHARLIE), VERIFIER_BOND-1) ;
+		assert_noop!(Owners::register_verifier(Origin::signed(account(CHARLIE))), Error::<Test>::NotEnoughBalanceToBond) ;
+	}) ;
+}
+
+#[test]
+fn unbonded_verifier_withdraws_after_the_unbonding_period() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		assert_noop!(Owners::withdraw_bond(Origin::signed(account(CHARLIE))), Error::<Test>::VerifierNotBonded) ;
+		assert_ok!(Owners::unregister_verifier(Origin::signed(account(CHARLIE)))) ;
+		let release_at = 1 + UNBONDING_PERIOD ;
+		assert_eq!(last_event(), RawEvent::VerifierUnbonding(account(CHARLIE), release_at)) ;
+		assert!(!Verifiers::<Test>::get(&account(CHARLIE)).1) ;
+		assert_noop!(Owners::unregister_verifier(Origin::signed(account(CHARLIE))), Error::<Test>::VerifierAlreadyUnbonding) ;
+
+		run_to_block(u64::from(release_at)-1) ;
+		assert_noop!(Owners::withdraw_bond(Origin::signed(account(CHARLIE))), Error::<Test>::UnbondingNotFinished) ;
+		run_to_block(u64::from(release_at)) ;
+		assert_ok!(Owners::withdraw_bond(Origin::signed(account(CHARLIE)))) ;
+		assert_eq!(last_event(), RawEvent::VerifierBondWithdrawn(account(CHARLIE), VERIFIER_BOND)) ;
+		assert!(!VerifierBonds::<Test>::contains_key(&account(CHARLIE))) ;
+		assert!(!Unbonding::<Test>::contains_key(&account(CHARLIE))) ;
+		assert_eq!(Balances::reserved_balance(&account(CHARLIE)), 0) ;
+		assert_eq!(Balances::free_balance(&account(CHARLIE)), INITIAL_BALANCE) ;
+
+		// Verifiers who left on their own can come back
+		register_charlie() ;
+	}) ;
+}
+
+#[test]
+fn missed_reveal_slashes_the_bond() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		verify_revealing(&url, &[(VERIFIER_1, true), (VERIFIER_2, true), (CHARLIE, true)], &[VERIFIER_1, VERIFIER_2]) ;
+		// SlashPrct is 10% by default
+		let slashed = VERIFIER_BOND/10 ;
+		assert!(System::events().iter().any(|record| record.event==Event::Owners(RawEvent::VerifierSlashed(account(CHARLIE), url.clone(), slashed)))) ;
+		assert_eq!(VerifierBonds::<Test>::get(&account(CHARLIE)), VERIFIER_BOND-slashed) ;
+		assert_eq!(Balances::reserved_balance(&account(CHARLIE)), VERIFIER_BOND-slashed) ;
+		assert_eq!(Owners::missed_reveals(&account(CHARLIE)), 1) ;
+		assert!(Verifiers::<Test>::get(&account(CHARLIE)).1) ;
+	}) ;
+}
+
+#[test]
+fn minority_vote_slashes_the_bond() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		// 75% agree, CHARLIE's only valid vote is in the minority
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, true), (VERIFIER_3, true), (CHARLIE, false)]) ;
+		assert_eq!(VerifierBonds::<Test>::get(&account(CHARLIE)), VERIFIER_BOND-VERIFIER_BOND/10) ;
+		assert_eq!(Owners::get_url_owner(&url), Some(account(BOB))) ;
+	}) ;
+}
+
+#[test]
+fn verifier_slashed_to_zero_is_suspended() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Owners::set_staking(Origin::root(), VERIFIER_BOND, UNBONDING_PERIOD, 100, 34, 0)) ;
+		register_charlie() ;
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		verify_revealing(&url, &[(VERIFIER_1, true), (VERIFIER_2, true), (CHARLIE, true)], &[VERIFIER_1, VERIFIER_2]) ;
+		assert_eq!(VerifierBonds::<Test>::get(&account(CHARLIE)), 0) ;
+		assert!(!Verifiers::<Test>::get(&account(CHARLIE)).1) ;
+
+		// Leaving and registering again doesn't lift the penalty
+		assert_ok!(Owners::unregister_verifier(Origin::signed(account(CHARLIE)))) ;
+		run_to_block(System::block_number() + u64::from(UNBONDING_PERIOD)) ;
+		assert_ok!(Owners::withdraw_bond(Origin::signed(account(CHARLIE)))) ;
+		assert_noop!(Owners::register_verifier(Origin::signed(account(CHARLIE))), Error::<Test>::VerifierSuspended) ;
+	}) ;
+}
+
+#[test]
+fn verifier_disabled_by_root_cannot_register_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Owners::disable_verifier(Origin::root(), account(VERIFIER_1))) ;
+		assert_eq!(last_event(), RawEvent::VerifierDisabled(account(VERIFIER_1))) ;
+		assert_noop!(Owners::register_verifier(Origin::signed(account(VERIFIER_1))), Error::<Test>::VerifierSuspended) ;
+
+		// Only root lifts the suspension
+		assert_ok!(Owners::enable_verifier(Origin::root(), account(VERIFIER_1))) ;
+		assert!(Verifiers::<Test>::get(&account(VERIFIER_1)).1) ;
+		assert_noop!(Owners::register_verifier(Origin::signed(account(VERIFIER_1))), Error::<Test>::VerifierAlreadyRegistered) ;
+	}) ;
+}
+
+#[test]
+fn unbonding_verifier_cannot_be_enabled() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		assert_ok!(Owners::unregister_verifier(Origin::signed(account(CHARLIE)))) ;
+		assert_noop!(Owners::enable_verifier(Origin::root(), account(CHARLIE)), Error::<Test>::VerifierAlreadyUnbonding) ;
+	}) ;
+}
+
+// Whether an owners event was deposited
+fn has_event(event: RawEvent<AccountId, u128>) -> bool {
+	System::events().iter().any(|record| record.event==Event::Owners(event.clone()))
+}
+
+#[test]
+fn request_without_reveals_is_fully_refunded() {
+	new_test_ext().execute_with(|| {
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		// Retried once at block 6, inconclusive at block 11
+		run_to_block(11) ;
+		assert_eq!(Owners::get_result(&url).map(|result| result.7), Some(RESULT_STATE_INCONCLUSIVE)) ;
+		assert!(has_event(RawEvent::RequestRefunded(account(BOB), url.clone(), REQUEST_PRICE))) ;
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE) ;
+	}) ;
+}
+
+#[test]
+fn inconclusive_request_is_partly_refunded() {
+	new_test_ext().execute_with(|| {
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, false)]) ;
+		assert_eq!(Owners::get_result(&url).map(|result| result.7), Some(RESULT_STATE_INCONCLUSIVE)) ;
+		assert!(has_event(RawEvent::RequestRefunded(account(BOB), url.clone(), REQUEST_PRICE/2))) ;
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE/2) ;
+		// Verifiers are not paid when they don't agree
+		assert_eq!(Balances::free_balance(&account(VERIFIER_1)), INITIAL_BALANCE) ;
+		assert_eq!(Balances::free_balance(&account(VERIFIER_2)), INITIAL_BALANCE) ;
+		assert_eq!(Owners::get_url_owner(&url), None) ;
+	}) ;
+}
+
+#[test]
+fn agreement_pays_majority_verifiers_only() {
+	new_test_ext().execute_with(|| {
+		register_charlie() ;
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, true), (VERIFIER_3, true), (CHARLIE, false)]) ;
+		assert_eq!(Owners::get_result(&url).map(|result| result.7), Some(RESULT_STATE_DECIDED)) ;
+		// VerifiersSharePrct is 50% by default
+		let share = REQUEST_PRICE/2/3 ;
+		for seed in &[VERIFIER_1, VERIFIER_2, VERIFIER_3] {
+			assert!(has_event(RawEvent::VerifierPaid(account(*seed), url.clone(), share))) ;
+			assert_eq!(Balances::free_balance(&account(*seed)), INITIAL_BALANCE+share) ;
+		}
+		assert_eq!(Balances::free_balance(&account(CHARLIE)), INITIAL_BALANCE-VERIFIER_BOND) ;
+		// Nothing is refunded once decided
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE) ;
+	}) ;
+}
+
+#[test]
+fn request_without_quorum_is_retried() {
+	new_test_ext().execute_with(|| {
+		let url = b"https://example.com/new".to_vec() ;
+		assert_ok!(Owners::request_url_check(Origin::signed(account(BOB)), url.clone(), ProofMethod::Body)) ;
+		// A single reveal doesn't reach NumChecksRequired
+		verify(&url, &[(VERIFIER_1, true)]) ;
+		assert_eq!(last_event(), RawEvent::VerificationRetried(url.clone(), 1)) ;
+		assert_eq!(Requests::<Test>::get(&url).0, 6) ;
+		assert_eq!(Owners::get_result(&url), None) ;
+
+		// The request is verified again from the retry block, keeping the fee
+		verify(&url, &[(VERIFIER_1, true), (VERIFIER_2, true)]) ;
+		assert_eq!(Owners::get_url_owner(&url), Some(account(BOB))) ;
+		assert_eq!(Balances::free_balance(&account(BOB)), INITIAL_BALANCE-REQUEST_PRICE) ;
+	}) ;
+}
@@ -106,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 110,
+	spec_version: 124,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 13,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -366,14 +366,26 @@ impl pallet_owners::Config for Runtime {
 	type Currency = pallet_balances::Pallet<Runtime>;
 	type MaxLengthURL = MaxLengthURL;
 	type NumChecksRequired = NumChecksRequired ;
+	type VoteWeighting = pallet_owners::ReputationWeight ;
 }
 
 // Pallet Likes
+parameter_types! {
+	pub const MaxClaimedLikes: u32 = 50;
+	pub const MaxSkippedPerLike: u32 = 20;
+	pub const MaxLikeHistory: u32 = 10;
+	pub const MaxLikesPerBatch: u32 = 20;
+}
 impl pallet_likes::Config for Runtime {
 	type Event = Event;
 	type Currency = pallet_balances::Pallet<Runtime>;
 	type MaxLengthURL = MaxLengthURL;
 	type OwnershipRegistry = pallet_owners::Module<Runtime> ;
+	type MaxClaimedLikes = MaxClaimedLikes ;
+	type MaxSkippedPerLike = MaxSkippedPerLike ;
+	type MaxLikeHistory = MaxLikeHistory ;
+	type MaxLikesPerBatch = MaxLikesPerBatch ;
+	type WeightInfo = pallet_likes::weights::SubstrateWeight<Runtime> ;
 }
 
 //==========WIKA-EDIT-STOP===========
@@ -396,8 +408,8 @@ construct_runtime!(
 
 		//==========WIKA-EDIT-START==========
         Authorities: pallet_authorities::{Pallet, Call, Storage, Config, Event<T>},
-        Likes: pallet_likes::{Pallet, Call, Storage, Event<T>},
-		Owners: pallet_owners::{Pallet, Call, Storage, Event<T>}
+        Likes: pallet_likes::{Pallet, Call, Storage, Config, Event<T>},
+		Owners: pallet_owners::{Pallet, Call, Storage, Config<T>, Event<T>}
 		//==========WIKA-EDIT-STOP===========
 	}
 );
@@ -545,6 +557,74 @@ impl_runtime_apis! {
 		}
 	}
 
+	//==========WIKA-EDIT-START==========
+	impl pallet_likes_rpc_runtime_api::LikesApi<Block, AccountId, Balance> for Runtime {
+		fn get_url(url: Vec<u8>) -> Option<pallet_likes_rpc_runtime_api::UrlInfo<AccountId, Balance>> {
+			Likes::get_url(&url).map(|(num_likes, first_in_line, last_in_line, owed_rewards)| {
+				pallet_likes_rpc_runtime_api::UrlInfo { num_likes, first_in_line, last_in_line, owed_rewards }
+			})
+		}
+
+		fn get_like(account: AccountId, url: Vec<u8>) -> Option<pallet_likes_rpc_runtime_api::LikeInfo<AccountId, Balance>> {
+			Likes::get_like(&account, &url).map(|(previous_likes, num_likes, remaining_rewards, remaining_rounds, claimable_rewards, next_in_line)| {
+				pallet_likes_rpc_runtime_api::LikeInfo { previous_likes, num_likes, remaining_rewards, remaining_rounds, claimable_rewards, next_in_line }
+			})
+		}
+
+		fn get_queue(url: Vec<u8>) -> Vec<pallet_likes_rpc_runtime_api::QueueItem<AccountId, Balance>> {
+			Likes::get_queue(&url).into_iter().map(|(account, remaining_rewards)| {
+				pallet_likes_rpc_runtime_api::QueueItem { account, remaining_rewards }
+			}).collect()
+		}
+
+		fn get_pending_rewards(account: AccountId) -> Balance {
+			Likes::pending_rewards(&account)
+		}
+
+		fn get_like_history(account: AccountId, url: Vec<u8>) -> Vec<pallet_likes_rpc_runtime_api::LikeRound<Balance>> {
+			Likes::get_like_history(&account, &url).into_iter().map(|(previous_likes, num_likes, round_rewards)| {
+				pallet_likes_rpc_runtime_api::LikeRound { previous_likes, num_likes, round_rewards }
+			}).collect()
+		}
+
+		fn get_like_summary(account: AccountId) -> pallet_likes_rpc_runtime_api::LikeSummary<Balance> {
+			let (total_liked, total_earned) = Likes::like_summary(&account) ;
+			pallet_likes_rpc_runtime_api::LikeSummary { total_liked, total_earned }
+		}
+	}
+
+	impl pallet_owners_rpc_runtime_api::OwnersApi<Block, AccountId, BlockNumber> for Runtime {
+		fn get_owner(url: Vec<u8>) -> Option<AccountId> {
+			Owners::get_url_owner(&url)
+		}
+
+		fn get_request(url: Vec<u8>) -> Option<pallet_owners_rpc_runtime_api::RequestInfo<AccountId, BlockNumber>> {
+			Owners::get_request_state(&url).map(|(requested_at, requester, phase, blocks_remaining)| {
+				let phase = match phase {
+					pallet_owners::REQUEST_PHASE_COMMIT => pallet_owners_rpc_runtime_api::RequestPhase::Commit,
+					pallet_owners::REQUEST_PHASE_REVEAL => pallet_owners_rpc_runtime_api::RequestPhase::Reveal,
+					_ => pallet_owners_rpc_runtime_api::RequestPhase::Aggregated,
+				};
+				pallet_owners_rpc_runtime_api::RequestInfo { requested_at, requester, phase, blocks_remaining }
+			})
+		}
+
+		fn get_result(url: Vec<u8>) -> Option<pallet_owners_rpc_runtime_api::ResultInfo<BlockNumber>> {
+			Owners::get_result(&url).map(|(block, num_votes, num_votes_yes, num_votes_majority, intro, proof, approved, state)| {
+				let state = match state {
+					pallet_owners::RESULT_STATE_DECIDED => pallet_owners_rpc_runtime_api::ResultState::Decided,
+					_ => pallet_owners_rpc_runtime_api::ResultState::Inconclusive,
+				} ;
+				pallet_owners_rpc_runtime_api::ResultInfo { block, num_votes, num_votes_yes, num_votes_majority, intro, proof, approved, state }
+			})
+		}
+
+		fn get_owned_urls(account: AccountId) -> Vec<Vec<u8>> {
+			Owners::get_owned_urls(&account)
+		}
+	}
+	//==========WIKA-EDIT-STOP===========
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -595,7 +675,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
-			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_likes, Likes);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok((batches, storage_info))